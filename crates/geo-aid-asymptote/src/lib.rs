@@ -0,0 +1,222 @@
+//! Geo-AID is capable of outputting its figures as Asymptote source code.
+
+use geo_aid_figure::math_string::{
+    push_latex_escaped, MathChar, MathIndex, MathSpecial, MathString, SPECIAL_MATH,
+};
+use geo_aid_figure::{
+    AngleItem, CircleItem, Color, Figure, Item, Label, LineItem, PointItem, Position, Style,
+    TwoPointItem,
+};
+use num_traits::ToPrimitive;
+use std::string::String;
+
+/// The radius of arcs marking angles.
+const ANGLE_ARC_RADIUS: f64 = 0.5;
+
+/// The distance between consecutive arcs marking the same angle.
+const ANGLE_ARC_SPACING: f64 = 0.1;
+
+/// The side length of squares marking right angles.
+const RIGHT_ANGLE_MARK_SIZE: f64 = 0.3;
+
+/// The Asymptote format writer.
+#[derive(Debug, Default)]
+pub struct Asymptote {
+    /// The current file contents
+    content: String,
+}
+
+impl Asymptote {
+    /// Get the figure in Asymptote format.
+    #[must_use]
+    pub fn draw(figure: &Figure) -> String {
+        let mut asy = Self {
+            content: String::from("unitsize(1cm);\n"),
+        };
+
+        for item in &figure.items {
+            match item {
+                Item::Point(point) => asy.draw_point(point),
+                Item::Line(line) => asy.draw_line(line),
+                Item::Ray(ray) => asy.draw_ray(ray),
+                Item::Segment(segment) => asy.draw_segment(segment),
+                Item::Circle(circle) => asy.draw_circle(circle),
+                Item::Angle(angle) => asy.draw_angle(angle),
+            }
+        }
+
+        // Lines and circles may reach past the canvas, hence the clip.
+        asy.content += &format!(
+            "clip(box((0,0), {}));\n",
+            Self::pair(Position {
+                x: figure.width,
+                y: figure.height
+            })
+        );
+
+        asy.content
+    }
+
+    /// Convert the given math string into a LaTeX string, used by Asymptote's labels.
+    /// Characters special to LaTeX are escaped, unless the string is raw.
+    fn math_to_latex(math: &MathString) -> String {
+        let mut s = String::new();
+
+        for c in math.iter().copied() {
+            match c {
+                MathChar::Ascii(c) if math.is_raw() => s.push(c),
+                MathChar::Ascii(c) => push_latex_escaped(&mut s, c),
+                MathChar::Special(special) => match special {
+                    MathSpecial::Quote => s += "\\\"",
+                    special => s += &format!("\\{}", SPECIAL_MATH[special.to_usize().unwrap()]),
+                },
+                MathChar::SetIndex(i) => {
+                    s += match i {
+                        MathIndex::Normal => "}",
+                        MathIndex::Lower => "_{",
+                    }
+                }
+                MathChar::Prime => s += "^{\\prime}",
+            }
+        }
+
+        s
+    }
+
+    /// Get the Asymptote pen of an item with the given [`Style`] and color.
+    /// Items without a color are black.
+    fn get_pen(style: Style, color: Option<Color>) -> String {
+        let style = match style {
+            Style::Dotted => "dotted",
+            Style::Dashed => "dashed",
+            Style::Bold => "linewidth(1.5)",
+            Style::Solid => "solid",
+        };
+
+        format!("{style}+{}", Self::get_color(color))
+    }
+
+    /// Get the Asymptote color of an item. Items without a color are black.
+    fn get_color(color: Option<Color>) -> String {
+        color.map_or_else(
+            || String::from("black"),
+            |color| format!("rgbint({},{},{})", color.r, color.g, color.b),
+        )
+    }
+
+    /// Format a position as an Asymptote pair.
+    fn pair(pos: Position) -> String {
+        format!("({},{})", pos.x, pos.y)
+    }
+
+    /// Draw a styled polyline going through the given points.
+    fn draw_polyline(
+        &mut self,
+        points: impl IntoIterator<Item = Position>,
+        style: Style,
+        color: Option<Color>,
+    ) {
+        let path = points
+            .into_iter()
+            .map(Self::pair)
+            .collect::<Vec<_>>()
+            .join("--");
+
+        self.content += &format!("draw({path}, {});\n", Self::get_pen(style, color));
+    }
+
+    /// Draw a ray or a segment, with an arrowhead at its second point if requested.
+    fn draw_two_point(&mut self, item: &TwoPointItem) {
+        self.content += &format!(
+            "draw({}--{}, {}{});\n",
+            Self::pair(item.points.0),
+            Self::pair(item.points.1),
+            Self::get_pen(item.style, item.color),
+            if item.arrow { ", Arrow" } else { "" }
+        );
+        self.draw_label(item.label.as_ref());
+    }
+
+    fn draw_point(&mut self, point: &PointItem) {
+        self.content += &format!(
+            "dot({}, {} + linewidth({} * linewidth()));\n",
+            Self::pair(point.position),
+            Self::get_color(point.color),
+            point.size
+        );
+
+        self.draw_label(point.label.as_ref());
+    }
+
+    /// Draw an item's label, if it has one.
+    fn draw_label(&mut self, label: Option<&Label>) {
+        if let Some(label) = label {
+            self.content += &format!(
+                "label(\"${}$\", {});\n",
+                Self::math_to_latex(&label.content),
+                Self::pair(label.position)
+            );
+        }
+    }
+
+    fn draw_line(&mut self, line: &LineItem) {
+        self.draw_polyline([line.points.0, line.points.1], line.style, line.color);
+        self.draw_label(line.label.as_ref());
+    }
+
+    fn draw_ray(&mut self, ray: &TwoPointItem) {
+        self.draw_two_point(ray);
+    }
+
+    fn draw_segment(&mut self, segment: &TwoPointItem) {
+        self.draw_two_point(segment);
+    }
+
+    fn draw_angle(&mut self, angle: &AngleItem) {
+        self.draw_label(angle.label.as_ref());
+
+        if angle.arcs == 0 {
+            return;
+        }
+
+        let origin = angle.points.1;
+
+        if angle.is_right {
+            let direction = |theta: f64| Position {
+                x: theta.cos(),
+                y: theta.sin(),
+            };
+            let arm1 = direction(angle.start) * RIGHT_ANGLE_MARK_SIZE;
+            let arm2 = direction(angle.end) * RIGHT_ANGLE_MARK_SIZE;
+
+            self.draw_polyline(
+                [origin + arm1, origin + arm1 + arm2, origin + arm2],
+                angle.style,
+                angle.color,
+            );
+            return;
+        }
+
+        // `arc` goes counterclockwise if the end angle is greater than the start one, clockwise otherwise.
+        for i in 0..angle.arcs {
+            self.content += &format!(
+                "draw(arc({}, {}, {}, {}), {});\n",
+                Self::pair(origin),
+                ANGLE_ARC_RADIUS + f64::from(i) * ANGLE_ARC_SPACING,
+                angle.start.to_degrees(),
+                angle.end.to_degrees(),
+                Self::get_pen(angle.style, angle.color)
+            );
+        }
+    }
+
+    fn draw_circle(&mut self, circle: &CircleItem) {
+        self.content += &format!(
+            "draw(circle({}, {}), {});\n",
+            Self::pair(circle.center),
+            circle.radius,
+            Self::get_pen(circle.style, circle.color)
+        );
+        self.draw_label(circle.label.as_ref());
+    }
+}
@@ -1,8 +1,8 @@
 use egui::{Color32, Context, RichText, Slider};
 use egui_file::FileDialog;
 use geo_aid_internal::engine::compiler::{self, Compiled, FigureFn};
-use geo_aid_internal::projector;
-use geo_aid_internal::projector::figure::{Item, Label, Position};
+use geo_aid_internal::projector::figure::{AngleItem, Item, Label, Position};
+use geo_aid_internal::projector::{self, ProjectionOptions};
 use geo_aid_internal::script::math;
 use geo_aid_internal::script::math::Flags;
 use geo_aid_math::{Context as MathContext, Func};
@@ -173,7 +173,7 @@ fn window_conf() -> Conf {
 fn draw_label(label: &Option<Label>, color: Color) {
     if let Some(label) = label {
         draw_text(
-            &label.content.to_string(),
+            label.content.to_string(),
             label.position.x as f32,
             label.position.y as f32,
             18.0,
@@ -193,6 +193,26 @@ fn draw_points(points: &(Position, Position), color: Color) {
     );
 }
 
+fn draw_angle_arc(angle: &AngleItem, color: Color) {
+    const RADIUS: f64 = 15.0;
+    const STEPS: u32 = 16;
+
+    let origin = angle.points.1;
+    let point_at = |i: u32| {
+        let dir = angle.start + (angle.end - angle.start) * f64::from(i) / f64::from(STEPS);
+        (
+            (origin.x + RADIUS * dir.cos()) as f32,
+            (origin.y + RADIUS * dir.sin()) as f32,
+        )
+    };
+
+    for i in 0..STEPS {
+        let (x1, y1) = point_at(i);
+        let (x2, y2) = point_at(i + 1);
+        draw_line(x1, y1, x2, y2, 1.0, color);
+    }
+}
+
 fn draw_figure(figure: &projector::figure::Figure, color: Color) {
     for item in &figure.items {
         match item {
@@ -220,6 +240,10 @@ fn draw_figure(figure: &projector::figure::Figure, color: Color) {
                 );
                 draw_label(&circle.label, color);
             }
+            Item::Angle(angle) => {
+                draw_angle_arc(angle, color);
+                draw_label(&angle.label, color);
+            }
         }
     }
 }
@@ -236,6 +260,7 @@ async fn main() {
                 (fig.figure_func)(&fig.current_state),
                 &fig.flags,
                 (screen_width() as f64 - 300.0, screen_height() as f64),
+                ProjectionOptions::default(),
             );
 
             draw_figure(&figure, BLACK);
@@ -252,6 +277,7 @@ async fn main() {
                 (fig.figure_func)(&state),
                 &fig.flags,
                 (screen_width() as f64 - 300.0, screen_height() as f64),
+                ProjectionOptions::default(),
             );
 
             draw_figure(&figure, GREEN);
@@ -7,7 +7,7 @@
 
 //! This crate contains type definitions for Geo-AID's JSON format.
 
-use crate::math_string::MathString;
+use crate::math_string::{MathString, ParseError};
 use num_rational::Rational64;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
@@ -17,6 +17,9 @@ use std::ops::{Add, Deref, DerefMut, Mul};
 /// Math strings are Geo-AID's way of handling text involving math-specific notation.
 pub mod math_string;
 
+/// Version of the JSON figure schema. Bumped on every change to the format.
+pub const SCHEMA_VERSION: u32 = 5;
+
 /// Index of an expression.
 /// Isn't `Copy` for easier differentiation between moving and cloning the value.
 #[allow(missing_copy_implementations)]
@@ -151,8 +154,26 @@ pub enum Style {
     Bold,
 }
 
+/// An RGB color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Color {
+    /// The red component
+    pub r: u8,
+    /// The green component
+    pub g: u8,
+    /// The blue component
+    pub b: u8,
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
 /// Label-related information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "LabelRepr", try_from = "LabelRepr")]
 pub struct Label {
     /// Where the label should be drawn (figure space)
     pub position: Position,
@@ -160,6 +181,45 @@ pub struct Label {
     pub content: MathString,
 }
 
+/// The JSON form of a [`Label`]. Raw contents are stored as-is and marked with a flag,
+/// since they are not valid math strings in general.
+#[derive(Serialize, Deserialize)]
+struct LabelRepr {
+    position: Position,
+    content: String,
+    #[serde(default, skip_serializing_if = "is_false")]
+    raw: bool,
+}
+
+impl From<Label> for LabelRepr {
+    fn from(label: Label) -> Self {
+        Self {
+            position: label.position,
+            raw: label.content.is_raw(),
+            content: if label.content.is_raw() {
+                label.content.to_string()
+            } else {
+                label.content.escaped()
+            },
+        }
+    }
+}
+
+impl TryFrom<LabelRepr> for Label {
+    type Error = ParseError;
+
+    fn try_from(repr: LabelRepr) -> Result<Self, Self::Error> {
+        Ok(Self {
+            position: repr.position,
+            content: if repr.raw {
+                MathString::raw(&repr.content)
+            } else {
+                repr.content.parse()?
+            },
+        })
+    }
+}
+
 /// A figure-space position
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Position {
@@ -241,6 +301,13 @@ pub enum ExpressionKind {
         /// Circle to query
         circle: VarIndex,
     },
+    /// A point rotated about the origin
+    Rotate {
+        /// The point to rotate
+        point: VarIndex,
+        /// The rotation angle (counterclockwise)
+        angle: VarIndex,
+    },
     /// Summation of numbers
     Sum {
         /// All the added ones
@@ -316,6 +383,26 @@ pub enum ExpressionKind {
         /// The point
         point: VarIndex,
     },
+    /// Sine of an angle
+    Sin {
+        /// The angle
+        angle: VarIndex,
+    },
+    /// Cosine of an angle
+    Cos {
+        /// The angle
+        angle: VarIndex,
+    },
+    /// Tangent of an angle
+    Tan {
+        /// The angle
+        angle: VarIndex,
+    },
+    /// Absolute value of a number
+    Abs {
+        /// The number
+        value: VarIndex,
+    },
     /// Line `pq`
     PointPoint {
         /// Point 1
@@ -401,6 +488,8 @@ pub enum Item {
     Segment(TwoPointItem),
     /// A circle
     Circle(CircleItem),
+    /// An angle
+    Angle(AngleItem),
 }
 
 impl Item {
@@ -424,6 +513,12 @@ pub struct PointItem {
     /// Whether to display the dot (circle)
     #[serde(default)]
     pub display_dot: bool,
+    /// The dot's size relative to the format's default dot size.
+    #[serde(default = "one_f64")]
+    pub size: f64,
+    /// The point's color. Black if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
     /// The point's label
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub label: Option<Label>,
@@ -439,6 +534,9 @@ pub struct LineItem {
     /// How the line should be drawn
     #[serde(default)]
     pub style: Style,
+    /// The line's color. Black if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
     /// The line's label
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub label: Option<Label>,
@@ -456,6 +554,12 @@ pub struct TwoPointItem {
     /// How the line should be drawn
     #[serde(default)]
     pub style: Style,
+    /// The item's color. Black if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    /// Whether to draw an arrowhead at the second point (the far end if ray).
+    #[serde(default)]
+    pub arrow: bool,
     /// The item's label
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub label: Option<Label>,
@@ -473,7 +577,64 @@ pub struct CircleItem {
     /// How the line should be drawn
     #[serde(default)]
     pub style: Style,
+    /// The circle's color. Black if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
     /// The circle's label
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub label: Option<Label>,
 }
+
+/// The radius of arcs marking angles, in figure space.
+pub const ANGLE_ARC_RADIUS: f64 = 15.0;
+
+/// The distance between consecutive arcs marking the same angle, in figure space.
+pub const ANGLE_ARC_SPACING: f64 = 3.0;
+
+/// The side length of squares marking right angles, in figure space.
+pub const RIGHT_ANGLE_MARK_SIZE: f64 = 10.0;
+
+/// An angle item. Usually depicted by an arc.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AngleItem {
+    /// The first arm's point, the origin and the second arm's point.
+    pub points: (Position, Position, Position),
+    /// The defining expression index
+    pub id: VarIndex,
+    /// The direction (in radians) at which the arc starts.
+    pub start: f64,
+    /// The direction (in radians) at which the arc ends. The arc is drawn
+    /// counterclockwise if `end` is greater than `start` and clockwise otherwise.
+    pub end: f64,
+    /// Whether the angle is a right angle, conventionally marked with a square instead of an arc.
+    #[serde(default)]
+    pub is_right: bool,
+    /// The number of arcs marking the angle.
+    #[serde(default = "one_u8")]
+    pub arcs: u8,
+    /// How the arc should be drawn
+    #[serde(default)]
+    pub style: Style,
+    /// The arc's color. Black if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    /// The angle's label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<Label>,
+}
+
+/// Used for serde defaults.
+fn one_u8() -> u8 {
+    1
+}
+
+/// Used for serde defaults.
+fn one_f64() -> f64 {
+    1.0
+}
+
+/// Used for serde to skip `false` flags.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_false(value: &bool) -> bool {
+    !value
+}
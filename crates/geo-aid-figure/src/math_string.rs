@@ -16,6 +16,28 @@ pub const SPECIAL_MATH: [&str; 49] = [
     "omega", "Omega", "quote",
 ];
 
+/// Greek letters in the order of [`SPECIAL_MATH`], for formats that display them as Unicode.
+pub const GREEK: [char; 48] = [
+    'α', 'Α', 'β', 'Β', 'γ', 'Γ', 'δ', 'Δ', 'ε', 'Ε', 'ζ', 'Ζ', 'η', 'Η', 'θ', 'Θ', 'ι', 'Ι', 'κ',
+    'Κ', 'λ', 'Λ', 'μ', 'Μ', 'ν', 'Ν', 'ξ', 'Ξ', 'ο', 'Ο', 'φ', 'Φ', 'ρ', 'Ρ', 'σ', 'Σ', 'τ', 'Τ',
+    'υ', 'Υ', 'φ', 'Φ', 'χ', 'Χ', 'ψ', 'Ψ', 'ω', 'Ω',
+];
+
+/// Pushes an ASCII character of a math string onto a LaTeX math mode string.
+/// Characters special to LaTeX are escaped.
+pub fn push_latex_escaped(target: &mut String, c: char) {
+    match c {
+        '#' | '%' | '&' | '_' | '$' | '{' | '}' => {
+            target.push('\\');
+            target.push(c);
+        }
+        '\\' => *target += "\\backslash ",
+        '^' => *target += "\\hat{}",
+        '~' => *target += "\\sim ",
+        c => target.push(c),
+    }
+}
+
 /// A span in a string. Does not account for lines
 #[derive(Debug, Clone, Copy, Default)]
 pub struct StringSpan {
@@ -245,25 +267,67 @@ impl Display for MathSpecial {
 
 /// A series of math characters.
 #[derive(Debug, Clone, Default)]
-pub struct MathString(Vec<MathChar>);
+pub struct MathString {
+    /// The characters
+    chars: Vec<MathChar>,
+    /// Whether the string was given raw and is meant to be passed to output formats as-is.
+    raw: bool,
+}
 
 impl MathString {
     /// Creates a new, empty math string
     #[must_use]
     pub fn new() -> Self {
-        Self(Vec::new())
+        Self {
+            chars: Vec::new(),
+            raw: false,
+        }
     }
 
     /// Checks if the math string is empty
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.chars.is_empty()
     }
 
     /// Creates a new string from a `&str`, treating each character literally
     #[must_use]
     pub fn raw(source: &str) -> Self {
-        Self(source.chars().map(MathChar::Ascii).collect())
+        Self {
+            chars: source.chars().map(MathChar::Ascii).collect(),
+            raw: true,
+        }
+    }
+
+    /// Checks if the string was created with [`MathString::raw`]. Raw strings may contain
+    /// intentional markup (e.g. LaTeX) and should not be escaped.
+    #[must_use]
+    pub fn is_raw(&self) -> bool {
+        self.raw
+    }
+
+    /// Writes the string in the form it is parsed from, so that parsing it gives back the same string.
+    /// Unlike [`Display`], escapes the characters that have special meaning in math strings.
+    #[must_use]
+    pub fn escaped(&self) -> String {
+        let mut s = String::new();
+
+        for c in &self.chars {
+            match c {
+                MathChar::Prime => s.push('\''),
+                MathChar::SetIndex(MathIndex::Normal) => s.push('}'),
+                MathChar::SetIndex(MathIndex::Lower) => s += "_{",
+                MathChar::Ascii(c @ ('\\' | '_' | '[' | '{' | '}' | '\'')) => s.extend(['\\', *c]),
+                MathChar::Ascii(c) => s.push(*c),
+                MathChar::Special(c) => {
+                    s.push('[');
+                    s += SPECIAL_MATH[*c as usize];
+                    s.push(']');
+                }
+            }
+        }
+
+        s
     }
 }
 
@@ -271,19 +335,19 @@ impl Deref for MathString {
     type Target = Vec<MathChar>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.chars
     }
 }
 
 impl DerefMut for MathString {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.chars
     }
 }
 
 impl Display for MathString {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for c in &self.0 {
+        for c in &self.chars {
             write!(f, "{c}")?;
         }
 
@@ -383,7 +447,10 @@ impl FromStr for MathString {
             });
         }
 
-        Ok(Self(math_string))
+        Ok(Self {
+            chars: math_string,
+            raw: false,
+        })
     }
 }
 
@@ -392,23 +459,7 @@ impl Serialize for MathString {
     where
         S: Serializer,
     {
-        let mut s = String::new();
-
-        for c in &self.0 {
-            match c {
-                MathChar::Prime => s.push('c'),
-                MathChar::SetIndex(MathIndex::Normal) => s.push('}'),
-                MathChar::SetIndex(MathIndex::Lower) => s += "_{",
-                MathChar::Ascii(c) => s.extend(['\\', *c]),
-                MathChar::Special(c) => {
-                    s.push('[');
-                    s += SPECIAL_MATH[*c as usize];
-                    s.push(']');
-                }
-            }
-        }
-
-        serializer.serialize_str(&self.to_string())
+        serializer.serialize_str(&self.escaped())
     }
 }
 
@@ -423,7 +474,7 @@ impl<'de> Deserialize<'de> for MathString {
 
 struct MathStringVisitor;
 
-impl<'de> Visitor<'de> for MathStringVisitor {
+impl Visitor<'_> for MathStringVisitor {
     type Value = MathString;
 
     fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
@@ -4,7 +4,7 @@ use std::io::{Seek, Write};
 use geo_aid_figure::math_string::{MathChar, MathIndex, MathSpecial, MathString, SPECIAL_MATH};
 use geo_aid_figure::{
     CircleItem, EntityIndex, EntityKind, Figure, Item, Label, LineItem, PointItem, Style,
-    TwoPointItem, Value,
+    TwoPointItem, Value, VarIndex,
 };
 use geo_aid_figure::{Expression, ExpressionKind};
 
@@ -132,6 +132,8 @@ impl<'f> Geogebra<'f> {
                 Item::Ray(ray) => ggb.draw_ray(ray),
                 Item::Segment(segment) => ggb.draw_segment(segment),
                 Item::Circle(circle) => ggb.draw_circle(circle),
+                // Angle marks are not exported to GeoGebra.
+                Item::Angle(_) => (),
             }
         }
 
@@ -164,6 +166,38 @@ impl<'f> Geogebra<'f> {
         s
     }
 
+    /// Check whether any of the given variables is a point.
+    fn has_point<'a>(&self, mut items: impl Iterator<Item = &'a VarIndex>) -> bool {
+        items.any(|v| self.variables[v.0].as_point().is_some())
+    }
+
+    /// Split the given points and numbers into lists of their x and y coordinates.
+    /// Numbers are split into their real and imaginary parts.
+    fn coordinates(&mut self, items: &[VarIndex]) -> (Var<List<Numeric>>, Var<List<Numeric>>) {
+        let mut xs = Vec::new();
+        let mut ys = Vec::new();
+
+        for item in items {
+            match &self.variables[item.0] {
+                VarKind::Point(point) => {
+                    // `PointAccess::y` gives the x coordinate in geogebra-types 0.1.5.
+                    xs.push(self.workspace.var(point.x()));
+                    ys.push(self.workspace.var(Point::from(point).y()));
+                }
+                VarKind::Number(number) => {
+                    xs.push(self.workspace.var(number.real()));
+                    ys.push(self.workspace.var(number.imaginary()));
+                }
+                VarKind::Line(_) | VarKind::Circle(_) => unreachable!(),
+            }
+        }
+
+        let xs: List<Numeric> = xs.iter().into();
+        let ys: List<Numeric> = ys.iter().into();
+
+        (self.workspace.var(xs), self.workspace.var(ys))
+    }
+
     fn load_expr(&mut self, expr: &Expression) {
         let var = match &expr.kind {
             ExpressionKind::Entity { id } => {
@@ -198,6 +232,28 @@ impl<'f> Geogebra<'f> {
                 let circle = self.variables[circle.0].as_circle().unwrap();
                 self.workspace.var(circle.center()).into()
             }
+            ExpressionKind::Rotate { point, angle } => {
+                // Rotation about the origin is a multiplication by `e^(i*angle)`.
+                let point = self.variables[point.0].as_point().unwrap().complex();
+                let angle = self.variables[angle.0].as_number().unwrap();
+
+                let rotated = self
+                    .workspace
+                    .var(point * Numeric::complex(angle.cos(), angle.sin()));
+
+                self.workspace
+                    .var((rotated.real(), rotated.imaginary()))
+                    .into()
+            }
+            ExpressionKind::Sum { plus, minus } if self.has_point(plus.iter().chain(minus)) => {
+                // Points are added as complex numbers, one coordinate at a time.
+                let (plus_x, plus_y) = self.coordinates(plus);
+                let (minus_x, minus_y) = self.coordinates(minus);
+
+                self.workspace
+                    .var((plus_x.sum() - minus_x.sum(), plus_y.sum() - minus_y.sum()))
+                    .into()
+            }
             ExpressionKind::Sum { plus, minus } => {
                 let plus: List<Numeric> = plus
                     .iter()
@@ -215,6 +271,22 @@ impl<'f> Geogebra<'f> {
 
                 self.workspace.var(plus_var.sum() - minus_var.sum()).into()
             }
+            ExpressionKind::Product { times, by } if self.has_point(times.iter()) => {
+                // The point and the factors are multiplied as complex numbers.
+                let complex = |v: &VarIndex| match &self.variables[v.0] {
+                    VarKind::Point(point) => point.complex(),
+                    VarKind::Number(number) => Numeric::complex(number.real(), number.imaginary()),
+                    VarKind::Line(_) | VarKind::Circle(_) => unreachable!(),
+                };
+
+                let product = times.iter().map(complex).reduce(|a, b| a * b).unwrap();
+                let product = by.iter().map(complex).fold(product, |a, b| a / b);
+                let product = self.workspace.var(product);
+
+                self.workspace
+                    .var((product.real(), product.imaginary()))
+                    .into()
+            }
             ExpressionKind::Product { times, by } => {
                 let times: List<Numeric> = times
                     .iter()
@@ -291,8 +363,28 @@ impl<'f> Geogebra<'f> {
                 self.workspace.var(point.x()).into()
             }
             ExpressionKind::PointY { point } => {
+                // `PointAccess::y` gives the x coordinate in geogebra-types 0.1.5.
                 let point = self.variables[point.0].as_point().unwrap();
-                self.workspace.var(point.y()).into()
+                self.workspace.var(Point::from(point).y()).into()
+            }
+            ExpressionKind::Sin { angle } => {
+                let angle = self.variables[angle.0].as_number().unwrap();
+                self.workspace.var(angle.sin()).into()
+            }
+            ExpressionKind::Cos { angle } => {
+                let angle = self.variables[angle.0].as_number().unwrap();
+                self.workspace.var(angle.cos()).into()
+            }
+            ExpressionKind::Tan { angle } => {
+                let angle = self.variables[angle.0].as_number().unwrap();
+                self.workspace.var(angle.sin() / angle.cos()).into()
+            }
+            ExpressionKind::Abs { value } => {
+                let value = self.variables[value.0].as_number().unwrap();
+                // The modulus, which for real numbers is the absolute value.
+                self.workspace
+                    .var((value.real().pow(2.0) + value.imaginary().pow(2.0)).pow(0.5))
+                    .into()
             }
             ExpressionKind::PointPoint { p, q } => {
                 let p = self.variables[p.0].as_point().unwrap();
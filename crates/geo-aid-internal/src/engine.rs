@@ -3,6 +3,7 @@
 //! Details on how each works is provided in the math doc,
 //! but generic, simplified descriptions are also provided here.
 
+use geo_aid_math::Func;
 use std::collections::VecDeque;
 
 pub mod compiler;
@@ -66,3 +67,31 @@ impl QualityRecord {
         self.quality
     }
 }
+
+/// How well the generated figure satisfies a single rule.
+#[derive(Debug, Clone)]
+pub struct RuleQuality {
+    /// The rule as written in the script. `None` for rules added by the compiler.
+    pub source: Option<String>,
+    /// The rule's quality, in range `[0, 1]`.
+    pub quality: f64,
+}
+
+/// Compute the quality of every rule for the given inputs, in the order of `sources`.
+pub(crate) fn compute_rule_qualities(
+    rule_fn: Func,
+    sources: &[Option<String>],
+    inputs: &[f64],
+) -> Vec<RuleQuality> {
+    let mut errors = vec![0.0; sources.len()];
+    rule_fn.call(inputs, &mut errors);
+
+    errors
+        .into_iter()
+        .zip(sources)
+        .map(|(error, source)| RuleQuality {
+            source: source.clone(),
+            quality: (-error).exp(),
+        })
+        .collect()
+}
@@ -27,8 +27,7 @@ pub struct Compiled {
     pub context: Context,
     /// The number of inputs of this figure.
     pub input_count: usize,
-    /// Errors of each rule (for debugging purposes)
-    #[allow(unused)]
+    /// Errors of each rule, not multiplied by the rule's weight
     pub rule_errors: Vec<CompiledExpr>,
 }
 
@@ -119,15 +118,18 @@ pub fn compile(intermediate: &Intermediate) -> Compiled {
         .adjusted
         .rules
         .iter()
-        .map(|rule| (rule, compiler.compile_rule(rule)))
+        .map(|rule| (rule, compiler.compile_rule_kind(&rule.kind)))
         .collect();
 
+    // Rule errors are kept unweighted, so that a weight doesn't make a rule look better or worse
+    // satisfied than it is.
     let rule_error_exprs = rule_errors.iter().map(|v| v.1).collect();
 
     // Gather entity errors
     let mut entity_errors = [Context::zero()].repeat(intermediate.adjusted.entities.len());
-    for (rule, quality) in rule_errors {
+    for (rule, error) in rule_errors {
         // println!("{rule}");
+        let quality = compiler.weigh(rule, error);
         for ent in &rule.entities {
             entity_errors[ent.0] = compiler.context.add(entity_errors[ent.0], quality);
         }
@@ -258,11 +260,10 @@ impl<'r> Compiler<'r> {
         }
     }
 
-    /// Compile the error function for the given rule.
-    fn compile_rule(&mut self, rule: &Rule) -> CompiledExpr {
-        let quality = self.compile_rule_kind(&rule.kind);
+    /// Multiply the compiled error of the given rule by its weight.
+    fn weigh(&mut self, rule: &Rule, error: CompiledExpr) -> CompiledExpr {
         let weight = self.context.constant(rule.weight.to_complex().real);
-        self.context.mul(quality, weight)
+        self.context.mul(error, weight)
     }
 
     /// Compile the sum of given expressions.
@@ -364,6 +365,17 @@ impl<'r> Compiler<'r> {
                 sum.div_real(len, &mut self.context).into()
             }
             ExprKind::CircleCenter { circle } => self.variables[circle.0].to_circle().center.into(),
+            ExprKind::Rotate { point, angle } => {
+                // point * e^(i * angle)
+                let point = self.variables[point.0].to_complex();
+                let angle = self.variables[angle.0].to_complex();
+                let rotation = ComplexExpr {
+                    real: self.context.cos(angle.real),
+                    imaginary: self.context.sin(angle.real),
+                };
+
+                point.mul(rotation, &mut self.context).into()
+            }
             ExprKind::Sum { plus, minus } => {
                 let plus = self.compile_sum(plus);
                 let minus = self.compile_sum(minus);
@@ -480,6 +492,26 @@ impl<'r> Compiler<'r> {
                 let point = self.variables[point.0].to_complex();
                 ComplexExpr::real(point.imaginary).into()
             }
+            ExprKind::Sin { angle } => {
+                let angle = self.variables[angle.0].to_complex();
+                ComplexExpr::real(self.context.sin(angle.real)).into()
+            }
+            ExprKind::Cos { angle } => {
+                let angle = self.variables[angle.0].to_complex();
+                ComplexExpr::real(self.context.cos(angle.real)).into()
+            }
+            ExprKind::Tan { angle } => {
+                // sin(angle) / cos(angle)
+                let angle = self.variables[angle.0].to_complex();
+                let sin = self.context.sin(angle.real);
+                let cos = self.context.cos(angle.real);
+                ComplexExpr::real(self.context.div(sin, cos)).into()
+            }
+            ExprKind::Abs { value } => {
+                // Scalars are always real.
+                let value = self.variables[value.0].to_complex();
+                ComplexExpr::real(self.context.abs(value.real)).into()
+            }
             ExprKind::PointPoint { p, q } => {
                 let p = self.variables[p.0].to_complex();
                 let q = self.variables[q.0].to_complex();
@@ -5,11 +5,12 @@
 
 use crate::engine::compiler::{Compiled, FigureFn};
 use crate::engine::thread_pool::ThreadPool;
-use crate::engine::QualityRecord;
+use crate::engine::{QualityRecord, RuleQuality};
 use crate::script::figure::Generated;
 use crate::script::math::Intermediate;
 use geo_aid_math::{Context, Func};
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::cell::Cell;
 use std::time::{Duration, Instant};
 
 /// Glide runtime.
@@ -22,8 +23,14 @@ pub struct Glide {
     gradient_fn: Func,
     /// Figure function
     figure_fn: FigureFn,
+    /// The function computing errors of each rule
+    rule_fn: Func,
+    /// The source of each rule
+    rule_sources: Vec<Option<String>>,
     /// Current best state
     inputs: Vec<f64>,
+    /// The random number generator used for sampling.
+    rng: StdRng,
 }
 
 /// Parameters modifying the behavior of Glide
@@ -40,6 +47,12 @@ pub struct Params {
     /// If the arithmetic mean of the last `mean_count` deltas ever goes below
     /// this number, the generation process stops.
     pub max_mean_delta: f64,
+    /// The seed for the random number generator. A random one is used if not given.
+    pub seed: Option<u64>,
+    /// The maximal number of descent steps for a single sample. Unlimited if not given.
+    pub max_iterations: Option<usize>,
+    /// If the figure's quality ever reaches this, generation is stopped.
+    pub target_quality: Option<f64>,
 }
 
 impl Glide {
@@ -51,7 +64,7 @@ impl Glide {
             errors,
             figure_fn,
             input_count,
-            ..
+            rule_errors,
         } = super::compiler::compile(intermediate);
 
         // let rule1 = rule_errors[0];
@@ -71,15 +84,29 @@ impl Glide {
         });
         let total_error = context.pow(total_error_power, mean_exponent.recip());
 
-        let mut rng = rand::thread_rng();
-        let inputs = (0..input_count).map(|_| rng.gen::<f64>() * 10.0 - 5.0);
+        let mut rng = params
+            .seed
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+        let inputs = (0..input_count)
+            .map(|_| rng.gen::<f64>() * 10.0 - 5.0)
+            .collect();
+
+        let rule_sources = intermediate
+            .adjusted
+            .rules
+            .iter()
+            .map(|rule| rule.source.clone())
+            .collect();
 
         Self {
             params,
             error_fn: context.compute([total_error]),
             gradient_fn: context.compute_gradient(total_error),
             figure_fn,
-            inputs: inputs.collect(),
+            rule_fn: context.compute(rule_errors),
+            rule_sources,
+            inputs,
+            rng,
         }
     }
 
@@ -102,22 +129,30 @@ impl Glide {
                 previous_gradient: vec![0.0; input_count],
                 candidate: vec![0.0; input_count],
                 quality_record: QualityRecord::new(params.mean_count, params.max_mean_delta),
+                max_iterations: params.max_iterations,
+                target_quality: params.target_quality,
             },
             None,
             descend,
         );
 
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
 
         let mut error = [0.0];
         self.error_fn.call(&self.inputs, &mut error);
-        let mut current_quality = (-error[0]).exp();
+        // Shared between the task and result handlers.
+        let current_quality = Cell::new((-error[0]).exp());
 
         let mut samples_launched = 0;
 
         pool.execute(
             |ctx| {
-                if samples_launched >= self.params.samples {
+                if samples_launched >= self.params.samples
+                    || self
+                        .params
+                        .target_quality
+                        .is_some_and(|target| current_quality.get() >= target)
+                {
                     return false;
                 }
 
@@ -130,9 +165,9 @@ impl Glide {
             },
             |ctx| {
                 let q = ctx.quality_record.get_quality();
-                if ctx.quality_record.get_quality() > current_quality {
+                if ctx.quality_record.get_quality() > current_quality.get() {
                     self.inputs.clone_from(&ctx.sample);
-                    current_quality = q;
+                    current_quality.set(q);
                 }
 
                 sample_complete();
@@ -147,6 +182,12 @@ impl Glide {
         (self.figure_fn)(&self.inputs)
     }
 
+    /// Get the quality of every rule in the current best state, in the order they were defined.
+    #[must_use]
+    pub fn get_rule_qualities(&self) -> Vec<RuleQuality> {
+        super::compute_rule_qualities(self.rule_fn, &self.rule_sources, &self.inputs)
+    }
+
     #[must_use]
     pub fn get_total_quality(&self) -> f64 {
         let mut q = [0.0];
@@ -171,6 +212,10 @@ struct GenerateContext {
     candidate: Vec<f64>,
     /// Last qualities of the figure
     quality_record: QualityRecord,
+    /// The maximal number of descent steps.
+    max_iterations: Option<usize>,
+    /// The quality at which descent stops.
+    target_quality: Option<f64>,
 }
 
 /// Initial speed (gradient coefficient)
@@ -191,6 +236,7 @@ fn descend(ctx: &mut GenerateContext) {
     ctx.previous_gradient.clone_from(&ctx.gradient);
     ctx.quality_record.clear();
     ctx.candidate.clone_from(&ctx.sample);
+    let mut iterations = 0;
 
     loop {
         while speed > SPEED_LIMIT {
@@ -225,7 +271,14 @@ fn descend(ctx: &mut GenerateContext) {
             speed /= 1.5;
         }
 
-        if ctx.quality_record.record((-error[0]).exp()) || speed < SPEED_LIMIT {
+        iterations += 1;
+        let quality = (-error[0]).exp();
+
+        if ctx.quality_record.record(quality)
+            || speed < SPEED_LIMIT
+            || ctx.max_iterations.is_some_and(|max| iterations >= max)
+            || ctx.target_quality.is_some_and(|target| quality >= target)
+        {
             // println!("Sample: {ctx.sample:?}");
             // println!("Gradient: {gradient:?}");
             // println!("Speed: {speed:?}");
@@ -6,9 +6,9 @@
 pub use self::generator::Generator;
 use crate::engine::compiler::{Compiled, FigureFn};
 use crate::engine::rage::generator::AdjustableTemplate;
+use crate::engine::RuleQuality;
 use crate::script::figure::Generated;
 use crate::script::math::Intermediate;
-#[allow(unused_imports)]
 use geo_aid_math::Func;
 use std::time::Duration;
 
@@ -20,8 +20,10 @@ pub struct Rage {
     generator: Generator,
     /// The figure function
     figure_fn: FigureFn,
-    // rule_fn: Func,
-    // rule_count: usize,
+    /// The function computing errors of each rule
+    rule_fn: Func,
+    /// The source of each rule
+    rule_sources: Vec<Option<String>>,
 }
 
 /// The engine's generation params
@@ -34,6 +36,8 @@ pub struct Params {
     pub samples: usize,
     /// How many threads to use
     pub worker_count: usize,
+    /// The seed for the random number generator. A random one is used if not given.
+    pub seed: Option<u64>,
 }
 
 impl Rage {
@@ -44,7 +48,7 @@ impl Rage {
             errors,
             figure_fn,
             input_count,
-            ..
+            rule_errors,
         } = super::compiler::compile(intermediate);
 
         let error_fn = context.compute(errors.iter().copied());
@@ -55,8 +59,13 @@ impl Rage {
             .map(AdjustableTemplate::from)
             .collect();
 
-        // let rule_count = rule_errors.len();
-        // let rule_fn = context.compute(rule_errors.into_iter());
+        let rule_fn = context.compute(rule_errors);
+        let rule_sources = intermediate
+            .adjusted
+            .rules
+            .iter()
+            .map(|rule| rule.source.clone())
+            .collect();
 
         // let mut dst = [1.0, 1.0];
         // error_fn.call(&[0.0, 0.0, 2.0, 2.0], &mut dst);
@@ -65,8 +74,8 @@ impl Rage {
         Self {
             generator: Generator::new(params, input_count, error_fn, &adjustables.into()),
             figure_fn,
-            // rule_fn,
-            // rule_count,
+            rule_fn,
+            rule_sources,
         }
     }
 
@@ -76,6 +85,8 @@ impl Rage {
             params.max_adjustment,
             params.mean_count,
             params.delta_max_mean,
+            params.max_cycles,
+            params.target_quality,
             params.progress_update,
         )
     }
@@ -95,11 +106,18 @@ impl Rage {
     /// Get the figure based on the current best state.
     pub fn get_figure(&mut self) -> Generated {
         let inputs = self.generator.get_state();
-        // let mut rule_qs = vec![0.0; self.rule_count];
-        // self.rule_fn.call(&inputs.inputs, &mut rule_qs);
-        // println!("Rule qualities: {:?}", rule_qs);
         (self.figure_fn)(&inputs.inputs)
     }
+
+    /// Get the quality of every rule in the current best state, in the order they were defined.
+    #[must_use]
+    pub fn get_rule_qualities(&self) -> Vec<RuleQuality> {
+        super::compute_rule_qualities(
+            self.rule_fn,
+            &self.rule_sources,
+            &self.generator.get_state().inputs,
+        )
+    }
 }
 
 /// The generation params
@@ -112,6 +130,10 @@ pub struct GenParams {
     /// If the arithemtic mean of the last `mean_count` quality deltas ever
     /// goes below this, generation is stopped
     pub delta_max_mean: f64,
+    /// The maximal number of generation cycles. Unlimited if not given.
+    pub max_cycles: Option<usize>,
+    /// If the figure's quality ever reaches this, generation is stopped.
+    pub target_quality: Option<f64>,
     /// Ran every generation step.
     pub progress_update: Box<dyn FnMut(f64)>,
 }
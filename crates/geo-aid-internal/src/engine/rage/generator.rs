@@ -11,6 +11,7 @@ use crate::engine::thread_pool::ThreadPool;
 use crate::geometry::Complex;
 use crate::script::math::EntityKind;
 use geo_aid_math::Func;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 mod magic_box;
 
@@ -26,6 +27,8 @@ struct GenerateContext {
     error_fn: Func,
     /// The exponent used for a generic mean for computing figure quality.
     mean_exponent: f64,
+    /// The random number generator used for adjustments.
+    rng: StdRng,
 }
 
 /// An attempt at adjusting the state.
@@ -34,6 +37,7 @@ fn adjust_and_check(ctx: &mut GenerateContext) {
         &mut ctx.current_state,
         ctx.adjustment_magnitude,
         &ctx.adjustable_template,
+        &mut ctx.rng,
     );
 
     #[allow(clippy::cast_precision_loss)]
@@ -78,6 +82,8 @@ pub struct Generator {
     delta: f64,
     /// Input count
     input_count: usize,
+    /// The random number generator seeding the adjustments.
+    rng: StdRng,
 }
 
 /// The kind of an adjustable (entity)
@@ -126,12 +132,16 @@ impl Generator {
         error_fn: Func,
         adjustables: &Arc<[AdjustableTemplate]>,
     ) -> Self {
+        let mut rng = params
+            .seed
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+
         // Randomize the first state
         let current_state = State {
             inputs: {
                 let mut v = Vec::new();
                 v.reserve_exact(input_count);
-                v.resize_with(input_count, || rand::random::<f64>() * 10.0 - 5.0);
+                v.resize_with(input_count, || rng.gen::<f64>() * 10.0 - 5.0);
                 v
             },
             qualities: {
@@ -154,6 +164,7 @@ impl Generator {
                     adjustable_template: temp,
                     error_fn,
                     mean_exponent: -params.strictness,
+                    rng: StdRng::seed_from_u64(0),
                 }
             },
             std::iter::from_fn(|| Some((Arc::clone(adjustables), current_state.clone()))),
@@ -165,6 +176,7 @@ impl Generator {
             pool,
             delta: 0.0,
             input_count,
+            rng,
         }
     }
 
@@ -181,11 +193,14 @@ impl Generator {
         let current_state = self.current_state.clone();
 
         let mut mags = magnitudes.iter().copied();
+        let rng = &mut self.rng;
         self.pool.execute(
             |ctx| {
                 if let Some(mag) = mags.next() {
                     ctx.adjustment_magnitude = mag;
                     ctx.current_state.clone_from(&current_state);
+                    // Each task gets its own seed, so that the outcome doesn't depend on thread scheduling.
+                    ctx.rng = StdRng::seed_from_u64(rng.gen());
                     true
                 } else {
                     false
@@ -217,7 +232,8 @@ impl Generator {
         magnitudes
     }
 
-    /// Performs generation cycles until the mean delta from the last `mean_count` deltas becomes less or equal to `max_mean`.
+    /// Performs generation cycles until the mean delta from the last `mean_count` deltas becomes less or equal to `max_mean`,
+    /// `max_cycles` cycles are performed or the quality reaches `target_quality`.
     /// Executes `cyclic` after the end of each cycle.
     ///
     /// # Returns
@@ -230,6 +246,8 @@ impl Generator {
         maximum_adjustment: f64,
         mean_count: usize,
         max_mean: f64,
+        max_cycles: Option<usize>,
+        target_quality: Option<f64>,
         mut cyclic: P,
     ) -> Duration {
         let magnitudes = self.bake_magnitudes(maximum_adjustment);
@@ -244,9 +262,14 @@ impl Generator {
         let mean_count_f = mean_count as f64;
 
         let mut duration = Duration::new(0, 0);
+        let mut cycles = 0;
 
-        while mean_delta > max_mean {
+        while mean_delta > max_mean
+            && max_cycles.is_none_or(|max| cycles < max)
+            && target_quality.is_none_or(|target| current_quality < target)
+        {
             duration += self.cycle_prebaked(&magnitudes);
+            cycles += 1;
 
             self.delta = self.get_total_quality() - current_quality;
             current_quality = self.get_total_quality();
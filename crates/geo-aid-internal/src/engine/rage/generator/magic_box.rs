@@ -6,6 +6,8 @@
 
 use std::f64::consts::PI;
 
+use rand::Rng;
+
 use super::{AdjustableTemplate, Complex, State};
 
 /// Performs an adjustment of all entities in a random direction.
@@ -14,10 +16,12 @@ use super::{AdjustableTemplate, Complex, State};
 /// * `current_state` - current values and errors of all inputs
 /// * `matrix` - adjustment results are written to this thing.
 /// * `adjustment_magnitude` - the magnitude to apply to the adjustment (how much of a jump to allow). Eta in the formula.
+/// * `rng` - the source of randomness for the adjustment directions.
 pub fn adjust(
     current_state: &mut State,
     adjustment_magnitude: f64,
     template: &[AdjustableTemplate],
+    rng: &mut impl Rng,
 ) {
     let it = template
         .iter()
@@ -28,7 +32,7 @@ pub fn adjust(
     for (template, error) in it {
         match template {
             AdjustableTemplate::Point => {
-                let direction = 2.0 * rand::random::<f64>() * PI;
+                let direction = 2.0 * rng.gen::<f64>() * PI;
 
                 let unit = Complex::new(direction.cos(), direction.sin());
                 let offset = unit * adjustment_magnitude * error;
@@ -38,11 +42,7 @@ pub fn adjust(
                 index += 2;
             }
             AdjustableTemplate::Real => {
-                let direction = if rand::random::<u8>() & 1 == 0 {
-                    1.0
-                } else {
-                    -1.0
-                };
+                let direction = if rng.gen::<u8>() & 1 == 0 { 1.0 } else { -1.0 };
 
                 // Adjust by a RELATIVE value based on quality and randomly chosen direction (+/-)
                 let val = current_state.inputs[index];
@@ -50,11 +50,7 @@ pub fn adjust(
                 index += 1;
             }
             AdjustableTemplate::Clip1d => {
-                let direction = if rand::random::<u8>() & 1 == 0 {
-                    1.0
-                } else {
-                    -1.0
-                };
+                let direction = if rng.gen::<u8>() & 1 == 0 { 1.0 } else { -1.0 };
 
                 // Adjust by an ABSOLUTE value based on quality and randomly chosen direction (+/-)
                 let val = current_state.inputs[index];
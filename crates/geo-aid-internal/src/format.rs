@@ -0,0 +1,164 @@
+//! Output formats and rendering generated figures in them.
+
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use geo_aid_asymptote::Asymptote;
+use geo_aid_geogebra::Geogebra;
+use geo_aid_json::Json;
+use geo_aid_latex::Latex;
+use geo_aid_plaintext::Plaintext;
+use geo_aid_png::Png;
+use geo_aid_svg::Svg;
+
+use crate::projector::{self, ProjectionOptions};
+use crate::script::figure::Generated;
+use crate::script::math::Flags;
+
+/// A format a figure can be rendered in.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Format {
+    /// LaTeX + tikz + tikz-euclide.
+    Latex,
+    /// Simple SVG
+    Svg,
+    /// JSON (machine-readable) format according to the Schema included in the repository.
+    Json,
+    /// Plain text (human-readable) format.
+    Plaintext,
+    /// Geogebra workspace format (*.ggb)
+    Geogebra,
+    /// PNG raster image.
+    Png,
+    /// Asymptote vector graphics source.
+    Asymptote,
+}
+
+impl Format {
+    /// All the formats.
+    pub const ALL: [Self; 7] = [
+        Self::Latex,
+        Self::Svg,
+        Self::Json,
+        Self::Plaintext,
+        Self::Geogebra,
+        Self::Png,
+        Self::Asymptote,
+    ];
+
+    /// The name of this format, as accepted by [`Format::from_str`].
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Latex => "latex",
+            Format::Svg => "svg",
+            Format::Json => "json",
+            Format::Plaintext => "plaintext",
+            Format::Geogebra => "geogebra",
+            Format::Png => "png",
+            Format::Asymptote => "asymptote",
+        }
+    }
+
+    /// A short, human-readable description of this format.
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Format::Latex => "LaTeX + tikz + tikz-euclide.",
+            Format::Svg => "Simple SVG",
+            Format::Json => {
+                "JSON (machine-readable) format according to the Schema included in the repository."
+            }
+            Format::Plaintext => "Plain text (human-readable) format.",
+            Format::Geogebra => "Geogebra workspace format (*.ggb)",
+            Format::Png => "PNG raster image.",
+            Format::Asymptote => "Asymptote vector graphics source.",
+        }
+    }
+
+    /// The canvas width used when none is given.
+    #[must_use]
+    pub fn default_width(self) -> f64 {
+        match self {
+            Format::Json => 1.0,
+            Format::Geogebra | Format::Plaintext => 5.0,
+            Format::Latex | Format::Asymptote => 10.0,
+            Format::Svg | Format::Png => 500.0,
+        }
+    }
+
+    /// The extension of files in this format.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Latex => "tex",
+            Format::Svg => "svg",
+            Format::Json => "json",
+            Format::Plaintext => "txt",
+            Format::Geogebra => "ggb",
+            Format::Png => "png",
+            Format::Asymptote => "asy",
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A format name that isn't recognised.
+#[derive(Debug, Clone)]
+pub struct UnknownFormat(pub String);
+
+impl Display for UnknownFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown format '{}'", self.0)
+    }
+}
+
+impl std::error::Error for UnknownFormat {}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    /// Get a format by its name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownFormat(s.to_string()))
+    }
+}
+
+/// Project the generated figure onto a canvas of the given size and write it to `target`
+/// in the given format.
+///
+/// # Errors
+/// Returns an error if the target file couldn't be written.
+pub fn render(
+    format: Format,
+    target: &Path,
+    canvas_size: (f64, f64),
+    generated: Generated,
+    flags: &Arc<Flags>,
+) -> io::Result<()> {
+    let rendered = projector::project(generated, flags, canvas_size, ProjectionOptions::default());
+
+    let mut file = File::create(target)?;
+
+    match format {
+        Format::Latex => file.write_all(Latex::draw(&rendered).as_bytes()),
+        Format::Json => file.write_all(Json::draw(&rendered).as_bytes()),
+        Format::Svg => file.write_all(Svg::draw(&rendered).as_bytes()),
+        Format::Plaintext => file.write_all(Plaintext::draw(&rendered).as_bytes()),
+        Format::Geogebra => Geogebra::draw(&rendered, file),
+        Format::Png => file.write_all(&Png::draw(&rendered)),
+        Format::Asymptote => file.write_all(Asymptote::draw(&rendered).as_bytes()),
+    }
+}
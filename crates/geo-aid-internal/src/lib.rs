@@ -1,6 +1,7 @@
 #![warn(clippy::pedantic)]
 
 pub mod engine;
+pub mod format;
 pub mod projector;
 pub use geo_aid_script as script;
 pub use geo_aid_script::geometry;
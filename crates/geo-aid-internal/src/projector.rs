@@ -4,18 +4,22 @@ use crate::geometry;
 use crate::geometry::{Circle, Complex, Line, ValueEnum};
 pub use geo_aid_figure as figure;
 use geo_aid_figure::{
-    CircleItem as RenderedCircle, Entity, Expression, Figure, Item as Rendered, Label,
-    LineItem as RenderedLine, PointItem as RenderedPoint, Position,
+    AngleItem as RenderedAngle, CircleItem as RenderedCircle, Entity, Expression, Figure,
+    Item as Rendered, Label, LineItem as RenderedLine, PointItem as RenderedPoint, Position,
     TwoPointItem as RenderedTwoPoint, VarIndex,
 };
 use std::f64::consts::PI;
 use std::sync::Arc;
 
 use crate::script::figure::{
-    CircleItem, Generated, Item, LineItem, PointItem, RayItem, SegmentItem,
+    AngleDefinition, AngleItem, CircleItem, Generated, Item, LineItem, PointItem, RayItem,
+    SegmentItem,
 };
 use crate::script::math::{EntityKind, Expr, ExprType, Flags};
 
+/// How far from the origin angle labels are placed (figure space).
+const ANGLE_LABEL_DISTANCE: f64 = 25.0;
+
 /// Projector context.
 struct Projector {
     /// Variables used by the figure
@@ -28,70 +32,39 @@ struct Projector {
     pub segments: Vec<(Complex, Complex)>,
     /// Circles visible on the picture.
     pub circles: Vec<Circle>,
+    /// How far from a right angle (in radians) an angle can be to still be marked as one.
+    pub right_angle_tolerance: f64,
 }
 
 impl Projector {
     /// Gets the intersection points of the line with the picture's frame.
+    /// The first returned point is the one further back along the line's direction.
     fn get_line_ends(&self, ln_c: Line) -> (Complex, Complex) {
-        fn choose_intersection(i: usize, j: usize) -> impl Fn(f64, &[Complex]) -> Complex {
-            move |width, intersections| {
-                let x = intersections[i];
+        // Points of the line are `origin + t * direction`. For each axis, find the range of `t`
+        // for which the point lies between the frame's edges.
+        let range = |origin: f64, direction: f64, size: f64| {
+            if direction.abs() < f64::EPSILON {
+                // Parallel to the edges, no constraint on this axis.
+                (f64::NEG_INFINITY, f64::INFINITY)
+            } else {
+                let t1 = -origin / direction;
+                let t2 = (size - origin) / direction;
 
-                if x.real > 0f64 && x.real < width {
-                    x
-                } else {
-                    intersections[j]
-                }
+                (f64::min(t1, t2), f64::max(t1, t2))
             }
-        }
-
-        // +--0--+
-        // |     |
-        // 1     2
-        // |     |
-        // +--3--+
-
-        let intersections = [
-            geometry::get_intersection(
-                ln_c,
-                geometry::get_line(
-                    Complex::new(0.0, self.height),
-                    Complex::new(1.0, self.height),
-                ),
-            ),
-            geometry::get_intersection(
-                ln_c,
-                geometry::get_line(Complex::new(0.0, 0.0), Complex::new(0.0, 1.0)),
-            ),
-            geometry::get_intersection(
-                ln_c,
-                geometry::get_line(Complex::new(self.width, 0.0), Complex::new(self.width, 1.0)),
-            ),
-            geometry::get_intersection(
-                ln_c,
-                geometry::get_line(Complex::new(0.0, 0.0), Complex::new(1.0, 0.0)),
-            ),
-        ];
-
-        // If the product of the real and imaginary is negative, line is "going down".
-        let a = ln_c.direction.imaginary * ln_c.direction.real;
+        };
 
-        #[allow(clippy::cast_precision_loss)]
-        if a < 0f64 {
-            // There must be one intersection with lines 0/1 and 2/3
-            let i1 = choose_intersection(0, 1)(self.width, &intersections);
+        let (x_min, x_max) = range(ln_c.origin.real, ln_c.direction.real, self.width);
+        let (y_min, y_max) = range(ln_c.origin.imaginary, ln_c.direction.imaginary, self.height);
 
-            let i2 = choose_intersection(3, 2)(self.width, &intersections);
+        // The visible part of the line is where both ranges overlap.
+        let t_min = f64::max(x_min, y_min);
+        let t_max = f64::min(x_max, y_max);
 
-            (i1, i2)
-        } else {
-            // There must be one intersection with lines 1/3 and 0/2
-            let i1 = choose_intersection(3, 1)(self.width, &intersections);
-
-            let i2 = choose_intersection(0, 2)(self.width, &intersections);
-
-            (i1, i2)
-        }
+        (
+            ln_c.origin + ln_c.direction * t_min,
+            ln_c.origin + ln_c.direction * t_max,
+        )
     }
 
     /// Get the point's label position relative to the point.
@@ -177,7 +150,7 @@ impl Projector {
             }
 
             // We get the bisector angle.
-            let bisector_angle = (label_vectors.1.arg() + label_vectors.0.arg()) / 2.0;
+            let bisector_angle = f64::midpoint(label_vectors.1.arg(), label_vectors.0.arg());
 
             // This is just the standard complex number formula.
             let bisector_vec = Complex::polar(
@@ -195,6 +168,80 @@ impl Projector {
             .into()
         }
     }
+
+    /// Get how far the given position is from everything else drawn on the figure:
+    /// the given obstacles (points and labels), segments and circles.
+    fn get_clearance(&self, pos: Complex, obstacles: &[Complex]) -> f64 {
+        let points = obstacles
+            .iter()
+            .map(|&obstacle| geometry::distance_pt_pt(pos, obstacle));
+        let segments = self
+            .segments
+            .iter()
+            .map(|&(a, b)| distance_pt_segment(pos, a, b));
+        let circles = self
+            .circles
+            .iter()
+            .map(|circle| (geometry::distance_pt_pt(pos, circle.center) - circle.radius).abs());
+
+        points
+            .chain(segments)
+            .chain(circles)
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Move a point's label away from collisions. The preferred offset (relative to the point)
+    /// is kept if nothing is closer than `min_clearance` to it. Otherwise, the offset is rotated
+    /// around the point in steps of 45 degrees and the direction with the most clearance is chosen.
+    fn avoid_label_collisions(
+        &self,
+        point: Complex,
+        preferred: Complex,
+        obstacles: &[Complex],
+        min_clearance: f64,
+    ) -> Complex {
+        if self.get_clearance(point + preferred, obstacles) >= min_clearance {
+            return preferred;
+        }
+
+        (0..8)
+            .map(|i| preferred * Complex::polar(f64::from(i) * PI / 4.0, 1.0))
+            .map(|offset| (offset, self.get_clearance(point + offset, obstacles)))
+            .fold((preferred, f64::NEG_INFINITY), |best, candidate| {
+                if candidate.1 > best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            })
+            .0
+    }
+}
+
+/// Get the distance between a point and a segment `ab`.
+fn distance_pt_segment(point: Complex, a: Complex, b: Complex) -> f64 {
+    if geometry::distance_pt_pt(a, b) < f64::EPSILON {
+        return geometry::distance_pt_pt(point, a);
+    }
+
+    // The position of the point's projection along the segment, `0` at `a` and `1` at `b`.
+    let t = ((point - a) / (b - a)).real.clamp(0.0, 1.0);
+    geometry::distance_pt_pt(point, a + (b - a) * t)
+}
+
+/// Get the label position of a straight item delimited by two points.
+/// The label is placed near the midpoint, offset along the normal.
+fn get_label_position_straight(points: (Position, Position), offset: f64) -> Position {
+    let a = Complex::from(points.0);
+    let b = Complex::from(points.1);
+    let normal = (b - a).normalize().mul_i();
+
+    ((a + b) / 2.0 + normal * offset).into()
+}
+
+/// Get the label position of a circle. The label is placed outside of the circle, up and to the right.
+fn get_label_position_circle(center: Position, radius: f64, offset: f64) -> Position {
+    (Complex::from(center) + Complex::polar(PI / 4.0, radius + offset)).into()
 }
 
 /// Converts a [`VarIndex`] to its value.
@@ -239,6 +286,7 @@ impl Project<Item> for Projector {
             Item::Line(v) => Rendered::Line(self.project(v)),
             Item::Ray(v) => Rendered::Ray(self.project(v)),
             Item::Segment(v) => Rendered::Segment(self.project(v)),
+            Item::Angle(v) => Rendered::Angle(self.project(v)),
         }
     }
 }
@@ -255,6 +303,8 @@ impl Project<PointItem> for Projector {
                 .into(),
             id: item.id,
             display_dot: item.display_dot,
+            size: item.size,
+            color: item.color,
             label: if item.label.is_empty() {
                 None
             } else {
@@ -279,6 +329,7 @@ impl Project<LineItem> for Projector {
             id: item.id,
             style: item.style,
             points: (points.0.into(), points.1.into()),
+            color: item.color,
             label: if item.label.is_empty() {
                 None
             } else {
@@ -304,6 +355,8 @@ impl Project<SegmentItem> for Projector {
             p_id: item.p_id,
             q_id: item.q_id,
             style: item.style,
+            color: item.color,
+            arrow: item.arrow,
             label: if item.label.is_empty() {
                 None
             } else {
@@ -348,6 +401,8 @@ impl Project<RayItem> for Projector {
             p_id: item.p_id,
             q_id: item.q_id,
             style: item.style,
+            color: item.color,
+            arrow: item.arrow,
             label: if item.label.is_empty() {
                 None
             } else {
@@ -374,6 +429,7 @@ impl Project<CircleItem> for Projector {
             center: center.into(),
             radius,
             style: item.style,
+            color: item.color,
             label: if item.label.is_empty() {
                 None
             } else {
@@ -386,44 +442,67 @@ impl Project<CircleItem> for Projector {
     }
 }
 
-// /// Function getting the points defining the angle from the Expression defining it.
-// ///
-// /// # Panics
-// /// If given invalid data.
-// fn get_angle_points(
-//     angle: &ExprKind,
-//     variables: &[Expr<ValueEnum>],
-// ) -> (Complex, Complex, Complex) {
-//     match angle {
-//         ExprKind::ThreePointAngle { p, q, r } => {
-//             let arm1 = variables[p.0].meta.as_complex().unwrap();
-//             let origin = variables[q.0].meta.as_complex().unwrap();
-//             let arm2 = variables[r.0].meta.as_complex().unwrap();
-//
-//             (arm1, origin, arm2)
-//         }
-//         ExprKind::ThreePointAngleDir { p, q, r } => {
-//             let arm1 = variables[p.0].meta.as_complex().unwrap();
-//             let origin = variables[q.0].meta.as_complex().unwrap();
-//             let arm2 = variables[r.0].meta.as_complex().unwrap();
-//
-//             (arm1, origin, arm2)
-//         }
-//         ExprKind::TwoLineAngle { k, l } => {
-//             let ev_ln1 = variables[k.0].meta.as_line().unwrap();
-//             let ev_ln2 = variables[l.0].meta.as_line().unwrap();
-//
-//             let origin = geometry::get_intersection(ev_ln1, ev_ln2);
-//
-//             (
-//                 ev_ln1.origin + ev_ln1.direction,
-//                 origin,
-//                 ev_ln2.origin + ev_ln2.direction,
-//             )
-//         }
-//         _ => unreachable!(),
-//     }
-// }
+impl Projector {
+    /// Get the points delimiting the angle: the first arm's point, the origin and the second arm's point.
+    fn get_angle_points(&self, definition: &AngleDefinition) -> (Complex, Complex, Complex) {
+        match definition {
+            AngleDefinition::ThreePoint(p, q, r) => (
+                self.un_var(p).unwrap(),
+                self.un_var(q).unwrap(),
+                self.un_var(r).unwrap(),
+            ),
+            AngleDefinition::TwoLine(k, l) => {
+                let k: Line = self.un_var(k).unwrap();
+                let l: Line = self.un_var(l).unwrap();
+                let origin = geometry::get_intersection(k, l);
+
+                (origin + k.direction, origin, origin + l.direction)
+            }
+        }
+    }
+}
+
+impl Project<AngleItem> for Projector {
+    type Result = RenderedAngle;
+
+    fn project(&mut self, item: AngleItem) -> Self::Result {
+        let (arm1, origin, arm2) = self.get_angle_points(&item.definition);
+
+        // The arc starts at the first arm and sweeps towards the second one.
+        // The sign of the sweep tells the side on which the arc is drawn.
+        let start = (arm1 - origin).arg();
+        let mut sweep = ((arm2 - origin) / (arm1 - origin)).arg();
+
+        // Directed angles always go counterclockwise, so they may be reflex.
+        if item.directed {
+            sweep = sweep.rem_euclid(2.0 * PI);
+        }
+
+        // The label is placed in the middle of the arc, pulled in on short arms.
+        let shorter_arm = f64::min((arm1 - origin).magnitude(), (arm2 - origin).magnitude());
+        let label_distance = f64::min(ANGLE_LABEL_DISTANCE, shorter_arm / 2.0);
+        let label_position = origin + Complex::polar(start + sweep / 2.0, label_distance);
+
+        RenderedAngle {
+            points: (arm1.into(), origin.into(), arm2.into()),
+            id: item.id,
+            start,
+            end: start + sweep,
+            is_right: (sweep.abs() - PI / 2.0).abs() <= self.right_angle_tolerance,
+            arcs: item.arcs,
+            style: item.style,
+            color: item.color,
+            label: if item.label.is_empty() {
+                None
+            } else {
+                Some(Label {
+                    content: item.label,
+                    position: label_position.into(),
+                })
+            },
+        }
+    }
+}
 
 /// Represents the transform used by the projector to fit all
 /// items on the canvas with a proper margin.
@@ -464,42 +543,38 @@ impl Transform {
     }
 }
 
-// /// Function that outputs the vector containing the angles.
-// ///
-// /// # Panics
-// /// It shouldn't panic.
-// fn angles(
-//     figure: &Figure,
-//     offset: Complex,
-//     scale: f64,
-//     size: Complex,
-//     args: &EvaluationArgs,
-// ) -> Vec<RenderedAngle> {
-//     let mut blueprint_angles = Vec::new();
-//     for ang in &figure.angles {
-//         let angle_points = get_angle_points(&ang.0, args);
-//         blueprint_angles.push(RenderedAngle {
-//             label: String::new(),
-//             points: (
-//                 transform(offset, scale, size, angle_points.0),
-//                 transform(offset, scale, size, angle_points.1),
-//                 transform(offset, scale, size, angle_points.2),
-//             ),
-//             no_arcs: ang.1,
-//             expr: Arc::clone(&ang.0),
-//             angle_value: ang.0.evaluate(args),
-//             style: ang.2,
-//         });
-//     }
-//     blueprint_angles
-// }
+/// Options controlling how the figure is fit on the canvas.
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectionOptions {
+    /// The margin left on each side of the canvas, as a fraction of the canvas' size.
+    pub margin: f64,
+    /// The scale to use instead of fitting the figure to the canvas.
+    pub scale: Option<f64>,
+    /// How far from a right angle (in radians) an angle can be to still be marked as one.
+    pub right_angle_tolerance: f64,
+}
+
+impl Default for ProjectionOptions {
+    fn default() -> Self {
+        Self {
+            margin: 0.05,
+            scale: None,
+            right_angle_tolerance: 1e-3,
+        }
+    }
+}
 
 /// Takes the figure and rendered adjustables and attempts to design a figure that can then be rendered in chosen format.
 ///
 /// # Panics
 /// Any panic is a bug.
 #[allow(clippy::too_many_lines)]
-pub fn project(figure: Generated, _flags: &Arc<Flags>, canvas_size: (f64, f64)) -> Figure {
+pub fn project(
+    figure: Generated,
+    _flags: &Arc<Flags>,
+    canvas_size: (f64, f64),
+    options: ProjectionOptions,
+) -> Figure {
     let mut entities: Vec<_> = figure.entities;
     let mut expressions: Vec<_> = figure.variables;
     let items = figure.items;
@@ -519,7 +594,27 @@ pub fn project(figure: Generated, _flags: &Arc<Flags>, canvas_size: (f64, f64))
                     circle.center + circle.radius * Complex::i(),
                 ]);
             }
-            _ => (),
+            Item::Segment(SegmentItem { p_id, q_id, .. }) => points.extend([
+                expressions[p_id.0].meta.as_complex().unwrap(),
+                expressions[q_id.0].meta.as_complex().unwrap(),
+            ]),
+            // The ray's origin must be visible, the rest is clipped to the frame anyway.
+            Item::Ray(RayItem { p_id, .. }) => {
+                points.push(expressions[p_id.0].meta.as_complex().unwrap());
+            }
+            Item::Angle(AngleItem {
+                definition: AngleDefinition::ThreePoint(_, q_id, _),
+                ..
+            }) => {
+                points.push(expressions[q_id.0].meta.as_complex().unwrap());
+            }
+            // Angles between lines are skipped just like the lines themselves:
+            // for near-parallel lines the intersection can be arbitrarily far away.
+            Item::Angle(AngleItem {
+                definition: AngleDefinition::TwoLine(..),
+                ..
+            })
+            | Item::Line(_) => (),
         }
     }
 
@@ -560,20 +655,22 @@ pub fn project(figure: Generated, _flags: &Arc<Flags>, canvas_size: (f64, f64))
 
     #[allow(clippy::cast_precision_loss)]
     let size1 = Complex::new(canvas_size.0, canvas_size.1);
-    let size09 = size1 * 0.9;
-    let size005 = size1 * 0.05;
+    let margin = size1 * options.margin;
+    let fit_size = size1 - margin * 2.0;
     let min_size = f64::min(canvas_size.0, canvas_size.1);
 
-    // The scaled frame should be at most (and equal for at least one dimension) 90% of the size of the desired image (margins for rendering).
-    let scale = f64::min(
-        size09.real / total_size.real,
-        size09.imaginary / total_size.imaginary,
-    );
+    // The scaled frame should be at most (and equal for at least one dimension) the size of the desired image without the margins.
+    let scale = options.scale.unwrap_or_else(|| {
+        f64::min(
+            fit_size.real / total_size.real,
+            fit_size.imaginary / total_size.imaginary,
+        )
+    });
 
     let transform = Transform {
         offset,
         scale,
-        margin: size005,
+        margin,
     };
 
     let ent_types: Vec<_> = entities
@@ -596,16 +693,13 @@ pub fn project(figure: Generated, _flags: &Arc<Flags>, canvas_size: (f64, f64))
         }
     }
 
-    let expr_types: Vec<_> = expressions
-        .iter()
-        .map(|t| t.get_type(&expressions, &entities))
-        .collect();
-    for (expr, ty) in expressions.iter_mut().zip(expr_types) {
+    // The stored type is used, as points bound to coordinates are sums of plain numbers.
+    for expr in &mut expressions {
         match &mut expr.meta {
             ValueEnum::Circle(circle) => *circle = transform.transform_circle(*circle),
             ValueEnum::Line(line) => *line = transform.transform_line(*line),
             ValueEnum::Complex(complex) => {
-                if ExprType::Point == ty {
+                if ExprType::Point == expr.ty {
                     *complex = transform.transform_point(*complex);
                 }
             }
@@ -622,14 +716,61 @@ pub fn project(figure: Generated, _flags: &Arc<Flags>, canvas_size: (f64, f64))
         height: size1.imaginary,
         segments: Vec::new(),
         circles: Vec::new(),
+        right_angle_tolerance: options.right_angle_tolerance,
     };
 
     let mut rendered: Vec<_> = items.into_iter().map(|v| projector.project(v)).collect();
 
-    for point in rendered.iter_mut().filter_map(Rendered::as_point_mut) {
-        let pos = point.position;
-        if let Some(label) = &mut point.label {
-            label.position = pos + projector.get_label_position_rel(pos.into(), min_size);
+    // Labels of other items are kept at the same distance as points' labels.
+    let label_offset = 15.0 * min_size / 500.0;
+
+    // Points' labels avoid other points and labels placed before them.
+    let mut obstacles: Vec<Complex> = rendered
+        .iter()
+        .filter_map(|item| match item {
+            Rendered::Point(point) => Some(point.position.into()),
+            _ => None,
+        })
+        .collect();
+    let mut point_index = 0;
+
+    for item in &mut rendered {
+        match item {
+            Rendered::Point(point) => {
+                let pos = point.position;
+                if let Some(label) = &mut point.label {
+                    let own = obstacles.remove(point_index);
+                    let preferred = projector.get_label_position_rel(pos.into(), min_size);
+                    let offset = projector.avoid_label_collisions(
+                        own,
+                        preferred.into(),
+                        &obstacles,
+                        label_offset / 2.0,
+                    );
+                    obstacles.insert(point_index, own);
+
+                    label.position = (own + offset).into();
+                    obstacles.push(own + offset);
+                }
+                point_index += 1;
+            }
+            Rendered::Line(line) => {
+                if let Some(label) = &mut line.label {
+                    label.position = get_label_position_straight(line.points, label_offset);
+                }
+            }
+            Rendered::Ray(item) | Rendered::Segment(item) => {
+                if let Some(label) = &mut item.label {
+                    label.position = get_label_position_straight(item.points, label_offset);
+                }
+            }
+            Rendered::Circle(circle) => {
+                if let Some(label) = &mut circle.label {
+                    label.position =
+                        get_label_position_circle(circle.center, circle.radius, label_offset);
+                }
+            }
+            Rendered::Angle(_) => (),
         }
     }
 
@@ -0,0 +1,876 @@
+//! Checks of the geometric properties of figures generated from the scripts in `tests/`.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::sync::Arc;
+
+use geo_aid_asymptote::Asymptote;
+use geo_aid_figure::{CircleItem, Color, Figure, Item, LineItem, Position};
+use geo_aid_geogebra::Geogebra;
+use geo_aid_internal::engine::glide::{self, Glide};
+use geo_aid_internal::format::{self, Format};
+use geo_aid_internal::geometry::Complex;
+use geo_aid_internal::projector::{self, ProjectionOptions};
+use geo_aid_internal::script::math::{self, Intermediate, RuleKind};
+use geo_aid_internal::script::{figure, Error};
+use geo_aid_json::Json;
+use geo_aid_latex::Latex;
+use geo_aid_png::Png;
+use zip::ZipArchive;
+
+/// How far from exact a checked property can be (in canvas units).
+const TOLERANCE: f64 = 1e-3;
+
+/// The path to `tests/<name>.geo`.
+fn path(name: &str) -> String {
+    format!("{}/../../tests/{name}.geo", env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Generates and projects the figure from `tests/<name>.geo`.
+fn generate(name: &str) -> Figure {
+    generate_from(&fs::read_to_string(path(name)).unwrap())
+}
+
+/// Generates and projects the figure from the given script.
+fn generate_from(script: &str) -> Figure {
+    let (glide, intermediate) = run(script);
+
+    projector::project(
+        glide.get_figure(),
+        &Arc::new(intermediate.flags),
+        (500.0, 500.0),
+        ProjectionOptions::default(),
+    )
+}
+
+/// Generates the figure from the given script.
+fn run(script: &str) -> (Glide, Intermediate) {
+    let intermediate = math::load_script(script).unwrap();
+
+    let mut glide = Glide::new(
+        glide::Params {
+            strictness: 2.0,
+            samples: 512,
+            worker_count: 8,
+            mean_count: 128,
+            max_mean_delta: 0.0001,
+            seed: Some(0),
+            max_iterations: None,
+            target_quality: None,
+        },
+        &intermediate,
+    );
+    glide.generate(|| {});
+
+    (glide, intermediate)
+}
+
+/// Finds the position of the point labeled `label`.
+fn point(figure: &Figure, label: &str) -> Position {
+    figure
+        .items
+        .iter()
+        .find_map(|item| match item {
+            Item::Point(p) if p.label.as_ref()?.content.to_string() == label => Some(p.position),
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("no point labeled {label}"))
+}
+
+/// Gets all drawn lines.
+fn lines(figure: &Figure) -> Vec<&LineItem> {
+    figure
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Line(l) => Some(l),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Gets all drawn circles.
+fn circles(figure: &Figure) -> Vec<&CircleItem> {
+    figure
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Circle(c) => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+fn sub(a: Position, b: Position) -> (f64, f64) {
+    (a.x - b.x, a.y - b.y)
+}
+
+fn unit((x, y): (f64, f64)) -> (f64, f64) {
+    let len = x.hypot(y);
+    (x / len, y / len)
+}
+
+fn len((x, y): (f64, f64)) -> f64 {
+    x.hypot(y)
+}
+
+fn dot(a: (f64, f64), b: (f64, f64)) -> f64 {
+    a.0 * b.0 + a.1 * b.1
+}
+
+fn cross(a: (f64, f64), b: (f64, f64)) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+/// Checks whether `p` lies on `line`.
+fn lies_on(p: Position, line: &LineItem) -> bool {
+    let dir = unit(sub(line.points.1, line.points.0));
+    cross(dir, sub(p, line.points.0)).abs() < TOLERANCE
+}
+
+/// The distance from `p` to the line through `a` and `b`.
+fn dst_to_line(p: Position, a: Position, b: Position) -> f64 {
+    cross(unit(sub(b, a)), sub(p, a)).abs()
+}
+
+#[test]
+fn external_bisector() {
+    let figure = generate("external-bisector");
+    let (a, b, c) = (
+        point(&figure, "A"),
+        point(&figure, "B"),
+        point(&figure, "C"),
+    );
+    let k = lines(&figure)[0];
+
+    // The external bisector goes through the vertex...
+    assert!(lies_on(b, k));
+
+    // ...and is perpendicular to the internal one.
+    let internal = {
+        let (ba, bc) = (unit(sub(a, b)), unit(sub(c, b)));
+        (ba.0 + bc.0, ba.1 + bc.1)
+    };
+    let dir = unit(sub(k.points.1, k.points.0));
+    assert!(dot(dir, unit(internal)).abs() < TOLERANCE);
+}
+
+#[test]
+fn collinear_needs_two_points() {
+    let errors = math::load_script("let A = Point();\nlet k = collinear(A);").unwrap_err();
+    assert!(matches!(
+        errors[..],
+        [Error::NotEnoughPoints {
+            at_least: 2,
+            got: 1,
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn polygon_needs_three_points() {
+    let errors = math::load_script("let A, B = Point();\nlet p = polygon(AB);").unwrap_err();
+    assert!(matches!(
+        errors[..],
+        [Error::NotEnoughPoints {
+            at_least: 3,
+            got: 2,
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn comparisons_need_matching_units() {
+    for op in ["=", ">"] {
+        let script = format!("let A, B, C = Point();\ndst(A, B) {op} angle(A, B, C);");
+        let errors = math::load_script(&script).unwrap_err();
+        assert!(
+            matches!(
+                &errors[..],
+                [Error::InconsistentTypes { expected, got, .. }]
+                    if expected.0 != got.0
+            ),
+            "{op}: {errors:?}"
+        );
+    }
+}
+
+#[test]
+fn sqrt_needs_even_exponents() {
+    let errors = math::load_script("let A, B = Point();\nlet x = sqrt(AB);").unwrap_err();
+    assert!(matches!(errors[..], [Error::OddUnitExponent { .. }]));
+}
+
+#[test]
+fn line_ends() {
+    let figure = generate("line-ends");
+    let on_frame = |p: Position| {
+        let inside = (-TOLERANCE..=figure.width + TOLERANCE).contains(&p.x)
+            && (-TOLERANCE..=figure.height + TOLERANCE).contains(&p.y);
+        let on_edge = p.x.abs() < TOLERANCE
+            || (p.x - figure.width).abs() < TOLERANCE
+            || p.y.abs() < TOLERANCE
+            || (p.y - figure.height).abs() < TOLERANCE;
+
+        inside && on_edge
+    };
+
+    let lines = lines(&figure);
+    assert_eq!(lines.len(), 4);
+
+    // Every line spans the whole canvas.
+    for line in lines {
+        let (a, b) = line.points;
+        assert!(on_frame(a) && on_frame(b), "{a:?}, {b:?}");
+        assert!((a.x - b.x).hypot(a.y - b.y) > TOLERANCE);
+    }
+}
+
+#[test]
+fn angle_near_parallel() {
+    let figure = generate("angle-near-parallel");
+    let points: Vec<_> = ["A", "B", "C", "D"]
+        .into_iter()
+        .map(|label| point(&figure, label))
+        .collect();
+
+    // The points are fit on the canvas instead of the lines' intersection.
+    let width = points.iter().map(|p| p.x).fold(f64::NEG_INFINITY, f64::max)
+        - points.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+    let height = points.iter().map(|p| p.y).fold(f64::NEG_INFINITY, f64::max)
+        - points.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+    assert!(f64::max(width / figure.width, height / figure.height) > 0.5);
+}
+
+#[test]
+fn alternative_rules() {
+    let figure = generate("alternative-rules");
+    let (d, e, f) = (
+        point(&figure, "D"),
+        point(&figure, "E"),
+        point(&figure, "F"),
+    );
+
+    // `collinear`'s own rule is not a part of the alternative.
+    assert!(cross(unit(sub(e, d)), sub(f, d)).abs() < TOLERANCE);
+}
+
+#[test]
+fn rule_weights() {
+    let script = fs::read_to_string(path("rule-weights")).unwrap();
+    let intermediate = math::load_script(&script).unwrap();
+
+    // Weighted rules reach the generator as they were written. `AB = BC` is turned
+    // into an exact constraint instead, which its increased weight doesn't prevent.
+    let mut weights: Vec<_> = intermediate
+        .adjusted
+        .rules
+        .iter()
+        .filter(|rule| rule.source.is_some())
+        .map(|rule| rule.weight.to_complex().real)
+        .collect();
+    weights.sort_by(f64::total_cmp);
+    assert_eq!(weights, [2.0, 3.0, 6.0]);
+
+    let figure = generate("rule-weights");
+    let (a, b, c) = (
+        point(&figure, "A"),
+        point(&figure, "B"),
+        point(&figure, "C"),
+    );
+    let (ba, bc) = (sub(a, b), sub(c, b));
+    let angle = cross(ba, bc).atan2(dot(ba, bc)).abs().to_degrees();
+
+    assert!((ba.0.hypot(ba.1) - bc.0.hypot(bc.1)).abs() < 1.0);
+    assert!((60.0..80.0).contains(&angle));
+}
+
+#[test]
+fn angle_arcs() {
+    let figure = generate("angle-arcs");
+    let angles: Vec<_> = figure
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Angle(a) => Some(a),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(angles.len(), 3);
+
+    for angle in &angles {
+        // The label sits in the middle of the arc.
+        let label = angle.label.as_ref().unwrap().position;
+        let middle = (angle.start + angle.end) / 2.0;
+        let to_label = unit(sub(label, angle.points.1));
+        assert!(dot(to_label, (middle.cos(), middle.sin())) > 1.0 - TOLERANCE);
+    }
+
+    // The directed angle is negative, so its arc is reflex and goes counterclockwise.
+    let sweeps: Vec<_> = angles.iter().map(|a| a.end - a.start).collect();
+    assert!(sweeps
+        .iter()
+        .any(|sweep| (sweep - 230_f64.to_radians()).abs() < 0.1));
+}
+
+#[test]
+fn constant_folding() {
+    let figure = generate("constant-folding");
+    let (a, b, c) = (
+        point(&figure, "A"),
+        point(&figure, "B"),
+        point(&figure, "C"),
+    );
+    let (ba, bc) = (sub(a, b), sub(c, b));
+
+    assert!((ba.0.hypot(ba.1) - bc.0.hypot(bc.1)).abs() < 1.0);
+    let angle = cross(ba, bc).atan2(dot(ba, bc)).abs().to_degrees();
+    assert!((angle - 90.0).abs() < 0.5, "{angle}");
+}
+
+#[test]
+fn fixed_coordinates() {
+    let (glide, _) = run(&fs::read_to_string(path("fixed")).unwrap());
+    let generated = glide.get_figure();
+    // Coordinates before the projection.
+    let coordinates = |label: &str| {
+        let id = generated
+            .items
+            .iter()
+            .find_map(|item| match item {
+                figure::Item::Point(p) if p.label.to_string() == label => Some(p.id.0),
+                _ => None,
+            })
+            .unwrap();
+        generated.variables[id].meta.as_complex().unwrap()
+    };
+    let near = |p: Complex, (x, y): (f64, f64), tolerance: f64| {
+        (p.real - x).hypot(p.imaginary - y) < tolerance
+    };
+    let (o, x, c) = (coordinates("O"), coordinates("X"), coordinates("C"));
+
+    // Fixed points are exactly where they were placed.
+    assert!(near(o, (0.0, 0.0), TOLERANCE), "{o:?}");
+    assert!(near(x, (1.0, 0.0), TOLERANCE), "{x:?}");
+    assert!(((x - o).magnitude() - 1.0).abs() < TOLERANCE);
+
+    // Only `C` is adjusted to fit them.
+    assert!(near(c, (0.5, 0.75_f64.sqrt()), 0.01), "{c:?}");
+}
+
+#[test]
+fn ratio() {
+    let figure = generate("ratio");
+    let (a, b, c, d) = (
+        point(&figure, "A"),
+        point(&figure, "B"),
+        point(&figure, "C"),
+        point(&figure, "D"),
+    );
+    let (ab, cd) = (sub(b, a), sub(d, c));
+
+    // `AB / CD` is unitless, so it compares directly against `2 / 3`.
+    let ratio = ab.0.hypot(ab.1) / cd.0.hypot(cd.1);
+    assert!((ratio - 2.0 / 3.0).abs() < 0.01, "{ratio}");
+}
+
+#[test]
+fn rule_strength() {
+    let script = fs::read_to_string(path("rule-strength")).unwrap();
+    let apex_angle = |figure: &Figure| {
+        let (a, b, c) = (point(figure, "A"), point(figure, "B"), point(figure, "C"));
+        let (ba, bc) = (sub(a, b), sub(c, b));
+        cross(ba, bc).atan2(dot(ba, bc)).abs().to_degrees()
+    };
+
+    let with_soft = apex_angle(&generate("rule-strength"));
+    let without_soft = apex_angle(&generate_from(
+        &script
+            .lines()
+            .filter(|line| !line.contains("soft"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    ));
+    let equal_strength = apex_angle(&generate_from(
+        &script
+            .replace("[strength = hard] ", "")
+            .replace("[strength = soft] ", ""),
+    ));
+
+    // The hard rule converges about as well with the soft preference as without it...
+    assert!((without_soft - 40.0).abs() < 1.0, "{without_soft}");
+    assert!((with_soft - 40.0).abs() < 5.0, "{with_soft}");
+    // ...while a rule of equal strength pulls the angle much further away.
+    assert!(
+        (equal_strength - 40.0).abs() > (with_soft - 40.0).abs() + 5.0,
+        "{equal_strength}"
+    );
+}
+
+#[test]
+fn rule_qualities_ignore_weights() {
+    let (glide, _) = run(&fs::read_to_string(path("rule-strength")).unwrap());
+    let qualities: Vec<_> = glide
+        .get_rule_qualities()
+        .into_iter()
+        .filter(|rule| rule.source.is_some())
+        .map(|rule| rule.quality)
+        .collect();
+
+    // The soft rule gives way to the hard one and ends up about 40 degrees off. Its quality
+    // must show that, even though its low weight makes the violation cheap for the engine.
+    let worst = qualities.iter().copied().fold(1.0, f64::min);
+    assert!(worst < 0.5, "{qualities:?}");
+}
+
+#[test]
+fn latex_escaping() {
+    let figure = generate("latex-escaping");
+    let latex = Latex::draw(&figure);
+
+    assert!(latex.contains(r"{$M\_1$}"), "{latex}");
+    assert!(latex.contains(r"{$50\%$}"), "{latex}");
+    // Raw labels are passed through as they are.
+    assert!(latex.contains(r"{$K_{\#}$}"), "{latex}");
+
+    // The raw flag must survive a JSON round trip.
+    let loaded = Json::load(&Json::draw(&figure)).unwrap();
+    assert_eq!(Latex::draw(&loaded), latex);
+}
+
+#[test]
+fn incircle() {
+    let figure = generate("incircle");
+    let (a, b, c) = (
+        point(&figure, "A"),
+        point(&figure, "B"),
+        point(&figure, "C"),
+    );
+    let i = point(&figure, "I");
+
+    let omega = circles(&figure)[0];
+    assert!(len(sub(omega.center, i)) < TOLERANCE);
+
+    // The incircle is tangent to all three sides.
+    for (p, q) in [(a, b), (b, c), (c, a)] {
+        assert!((dst_to_line(i, p, q) - omega.radius).abs() < TOLERANCE);
+    }
+}
+
+#[test]
+fn orthocenter() {
+    let figure = generate("orthocenter");
+    let (a, b, c) = (
+        point(&figure, "A"),
+        point(&figure, "B"),
+        point(&figure, "C"),
+    );
+    let h = point(&figure, "H");
+
+    // The altitude from each vertex passes through the orthocenter.
+    for (vertex, p, q) in [(a, b, c), (b, c, a), (c, a, b)] {
+        assert!(dot(sub(h, vertex), unit(sub(q, p))).abs() < TOLERANCE);
+    }
+}
+
+#[test]
+fn nine_point_circle() {
+    let figure = generate("nine-point-circle");
+    let (a, b, c) = (
+        point(&figure, "A"),
+        point(&figure, "B"),
+        point(&figure, "C"),
+    );
+    let h = point(&figure, "H");
+    let mid = |p: Position, q: Position| Position {
+        x: (p.x + q.x) / 2.0,
+        y: (p.y + q.y) / 2.0,
+    };
+
+    let omega = circles(&figure)[0];
+    let feet = ["D", "E", "F"].map(|label| point(&figure, label));
+    let euler_midpoints = [a, b, c].map(|vertex| mid(vertex, h));
+    let side_midpoints = [mid(a, b), mid(b, c), mid(c, a)];
+
+    for p in feet
+        .into_iter()
+        .chain(euler_midpoints)
+        .chain(side_midpoints)
+    {
+        assert!((len(sub(p, omega.center)) - omega.radius).abs() < TOLERANCE);
+    }
+}
+
+#[test]
+fn tangent_at() {
+    let figure = generate("tangent-at");
+    let a = point(&figure, "A");
+    let omega = circles(&figure)[0];
+    let k = lines(&figure)[0];
+
+    // The tangent touches the circle at A and is perpendicular to the radius there.
+    assert!(lies_on(a, k));
+    let dir = unit(sub(k.points.1, k.points.0));
+    assert!(dot(dir, unit(sub(a, omega.center))).abs() < TOLERANCE);
+}
+
+#[test]
+fn parallel_perpendicular_rules() {
+    let script = fs::read_to_string(path("parallel-perpendicular-rules")).unwrap();
+    let intermediate = math::load_script(&script).unwrap();
+    let kinds: Vec<_> = intermediate
+        .adjusted
+        .rules
+        .iter()
+        .filter(|rule| rule.source.is_some())
+        .map(|rule| &rule.kind)
+        .collect();
+
+    // `parallel` and `perpendicular` are angle equalities, and `!parallel` is the inverse of one.
+    assert!(
+        matches!(
+            kinds[..],
+            [
+                RuleKind::NumberEq(..),
+                RuleKind::NumberEq(..),
+                RuleKind::Invert(inverted)
+            ] if matches!(**inverted, RuleKind::NumberEq(..))
+        ),
+        "{kinds:?}"
+    );
+}
+
+#[test]
+fn reports_every_syntax_error() {
+    let script = r#"
+        let A = Point() [label = "A"; size = +];
+        @optimizations: { @x: + }
+        let B = Point();
+        AB = = 5;
+    "#;
+
+    // Skipping an invalid statement must not stop inside brackets or run into the next one.
+    let errors = math::load_script(script).unwrap_err();
+    assert_eq!(errors.len(), 3, "{errors:?}");
+}
+
+/// Reads the definitions of the captioned objects from a GeoGebra file. References to
+/// other objects are replaced with their definitions, or captions, if they have one.
+fn ggb_definitions(ggb: Vec<u8>) -> HashMap<String, String> {
+    fn inline(
+        exp: &str,
+        expressions: &HashMap<String, String>,
+        captions: &HashMap<String, String>,
+    ) -> String {
+        let mut inlined = String::new();
+        let mut rest = exp;
+
+        while let Some(start) = rest.find("elem") {
+            inlined += &rest[..start];
+            rest = &rest[start..];
+
+            let end = rest[4..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(rest.len(), |i| i + 4);
+            let label = &rest[..end];
+            inlined += &captions
+                .get(label)
+                .cloned()
+                .unwrap_or_else(|| inline(&expressions[label], expressions, captions));
+            rest = &rest[end..];
+        }
+
+        inlined + rest
+    }
+
+    let mut xml = String::new();
+    ZipArchive::new(Cursor::new(ggb))
+        .unwrap()
+        .by_name("geogebra.xml")
+        .unwrap()
+        .read_to_string(&mut xml)
+        .unwrap();
+
+    let attribute = |tag: &str, name: &str| {
+        let start = tag.find(&format!("{name}=\"")).unwrap() + name.len() + 2;
+        tag[start..].split('"').next().unwrap().to_string()
+    };
+
+    let mut expressions = HashMap::new();
+    let mut captions = HashMap::new();
+    let mut element = String::new();
+    for tag in xml.split('<') {
+        if tag.starts_with("expression ") {
+            expressions.insert(attribute(tag, "label"), attribute(tag, "exp"));
+        } else if tag.starts_with("element ") {
+            element = attribute(tag, "label");
+        } else if tag.starts_with("caption val") {
+            let caption = attribute(tag, "val").trim_matches('$').to_string();
+            captions.insert(element.clone(), caption);
+        }
+    }
+
+    captions
+        .iter()
+        .map(|(label, caption)| {
+            let definition = inline(&expressions[label], &expressions, &captions);
+            (caption.clone(), definition)
+        })
+        .collect()
+}
+
+#[test]
+fn geogebra_reflect() {
+    let figure = generate("reflect");
+    let mut ggb = Cursor::new(Vec::new());
+    Geogebra::draw(&figure, &mut ggb).unwrap();
+    let definitions = ggb_definitions(ggb.into_inner());
+
+    // Reflections are `2 * center - P`, with the point multiplied as a complex number
+    // and the sum computed for both coordinates.
+    let two = "(real((2 + 0i) + (0 + 0i)i)) + (imaginary((2 + 0i) + (0 + 0i)i))i";
+    let reflection = |center: &str| {
+        let product = format!("(ToComplex({center})) * ({two})");
+        let doubled = format!("(real(real({product})), real(imaginary({product})))");
+        format!(
+            "(real((Sum(Append({{x({doubled})}}, 0 + 0i))) - (Sum(Append({{x(A)}}, 0 + 0i)))), \
+             real((Sum(Append({{y({doubled})}}, 0 + 0i))) - (Sum(Append({{y(A)}}, 0 + 0i)))))"
+        )
+    };
+
+    assert_eq!(
+        definitions["D"],
+        reflection("Intersect(Line(B, C), PerpendicularLine(A, Line(B, C)))")
+    );
+    assert_eq!(definitions["E"], reflection("B"));
+}
+
+#[test]
+fn projection_bounds() {
+    let figure = generate("projection-bounds");
+    let inside = |p: Position| {
+        (-TOLERANCE..=figure.width + TOLERANCE).contains(&p.x)
+            && (-TOLERANCE..=figure.height + TOLERANCE).contains(&p.y)
+    };
+
+    for item in &figure.items {
+        let fits = match item {
+            Item::Point(p) => inside(p.position),
+            Item::Circle(c) => {
+                let corner = |sign: f64| Position {
+                    x: c.center.x + sign * c.radius,
+                    y: c.center.y + sign * c.radius,
+                };
+                inside(corner(-1.0)) && inside(corner(1.0))
+            }
+            Item::Line(l) => inside(l.points.0) && inside(l.points.1),
+            Item::Ray(s) | Item::Segment(s) => inside(s.points.0) && inside(s.points.1),
+            Item::Angle(a) => inside(a.points.0) && inside(a.points.1) && inside(a.points.2),
+        };
+
+        assert!(fits, "{item:?} is outside the canvas");
+    }
+}
+
+#[test]
+fn png_output() {
+    let figure = generate_from("let A, B, C = Point();\nlet s = Segment(A, B);");
+    let png = Png::draw(&figure);
+
+    assert_eq!(png[..8], *b"\x89PNG\r\n\x1a\n");
+    // The IHDR chunk comes first and starts with the width and height.
+    assert_eq!(png[12..16], *b"IHDR");
+    let width = u32::from_be_bytes(png[16..20].try_into().unwrap());
+    let height = u32::from_be_bytes(png[20..24].try_into().unwrap());
+    assert_eq!((width, height), (500, 500));
+}
+
+#[test]
+fn asymptote_output() {
+    let figure = generate_from("let A, B = Point();\nlet s = Segment(A, B);");
+    let asy = Asymptote::draw(&figure);
+    let count = |start: &str| asy.lines().filter(|line| line.starts_with(start)).count();
+
+    assert!(asy.starts_with("unitsize(1cm);\n"));
+    assert_eq!(count("dot("), 2);
+    assert_eq!(count("label(\"$A$\""), 1);
+    assert_eq!(count("label(\"$B$\""), 1);
+    assert_eq!(count("draw("), 1);
+    assert!(asy.ends_with("clip(box((0,0), (500,500)));\n"));
+}
+
+#[test]
+fn rule_kinds() {
+    let script = fs::read_to_string(path("rule-kinds")).unwrap();
+    let intermediate = math::load_script(&script).unwrap();
+    let kinds: Vec<_> = intermediate
+        .adjusted
+        .rules
+        .iter()
+        .filter(|rule| rule.source.is_some())
+        .map(|rule| &rule.kind)
+        .collect();
+
+    // `<` is lowered as `>` with its sides swapped.
+    assert!(
+        matches!(
+            kinds[..],
+            [
+                RuleKind::PointEq(..),
+                RuleKind::NumberEq(..),
+                RuleKind::Gt(gt_a, gt_b),
+                RuleKind::Gt(lt_a, lt_b),
+                RuleKind::Invert(point_neq),
+                RuleKind::Invert(number_neq),
+                RuleKind::Alternative(alternative),
+            ] if (gt_a, gt_b) == (lt_b, lt_a)
+                && matches!(**point_neq, RuleKind::PointEq(..))
+                && matches!(**number_neq, RuleKind::NumberEq(..))
+                && matches!(alternative[..], [RuleKind::NumberEq(..), RuleKind::Gt(..)])
+        ),
+        "{kinds:?}"
+    );
+}
+
+#[test]
+fn ccw_weight() {
+    let script = fs::read_to_string(path("ccw"))
+        .unwrap()
+        .replace("ccw(ABC)", "ccw(ABC) [weight = 3]");
+    let intermediate = math::load_script(&script).unwrap();
+    let weights: Vec<_> = intermediate
+        .adjusted
+        .rules
+        .iter()
+        .filter(|rule| matches!(rule.kind, RuleKind::Gt(..)))
+        .map(|rule| rule.weight.to_complex().real)
+        .collect();
+
+    assert_eq!(weights, [3.0]);
+}
+
+#[test]
+fn in_range() {
+    let script = fs::read_to_string(path("in-range")).unwrap();
+    let intermediate = math::load_script(&script).unwrap();
+    let kinds: Vec<_> = intermediate
+        .adjusted
+        .rules
+        .iter()
+        .filter(|rule| rule.source.is_some())
+        .map(|rule| &rule.kind)
+        .collect();
+
+    // A bound on both sides, or, inverted, an alternative of the two complementary ones.
+    assert!(
+        matches!(
+            kinds[..],
+            [
+                ..,
+                RuleKind::Gt(..),
+                RuleKind::Gt(..),
+                RuleKind::Alternative(outside),
+            ] if matches!(outside[..], [RuleKind::Gt(..), RuleKind::Gt(..)])
+        ),
+        "{kinds:?}"
+    );
+
+    let figure = generate("in-range");
+    let (a, b, c) = (
+        point(&figure, "A"),
+        point(&figure, "B"),
+        point(&figure, "C"),
+    );
+    let angle = |p: Position, q: Position, r: Position| {
+        let (qp, qr) = (sub(p, q), sub(r, q));
+        cross(qp, qr).atan2(dot(qp, qr)).abs().to_degrees()
+    };
+
+    let apex = angle(b, a, c);
+    assert!((30.0..60.0).contains(&apex), "{apex}");
+    let base = angle(a, b, c);
+    assert!(!(70.0..80.0).contains(&base), "{base}");
+}
+
+#[test]
+fn rotate() {
+    let figure = generate("rotate");
+    let (a, b, c, d) = (
+        point(&figure, "A"),
+        point(&figure, "B"),
+        point(&figure, "C"),
+        point(&figure, "D"),
+    );
+    // A counterclockwise quarter turn about the origin.
+    let quarter = |(x, y): (f64, f64)| (-y, x);
+    let close = |p: (f64, f64), q: (f64, f64)| len((p.0 - q.0, p.1 - q.1)) < TOLERANCE;
+
+    // `D = rotate(B, A, 90deg)`
+    assert!(close(sub(d, a), quarter(sub(b, a))));
+    // `C = rotate(A, B, -90deg)`, so `A` is `C` rotated by 90deg about `B`.
+    assert!(close(sub(a, b), quarter(sub(c, b))));
+}
+
+#[test]
+fn point_size() {
+    let figure = generate("point-size");
+    let size = |label: &str| {
+        figure
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Item::Point(p) if p.label.as_ref()?.content.to_string() == label => {
+                    Some((p.size, p.color))
+                }
+                _ => None,
+            })
+            .unwrap()
+    };
+
+    assert_eq!(size("A"), (3.0, Some(Color { r: 255, g: 0, b: 0 })));
+    assert_eq!(size("B"), (0.5, None));
+    assert_eq!(size("C"), (1.0, None));
+}
+
+#[test]
+fn arrows() {
+    let figure = generate("arrows");
+    let arrows: Vec<_> = figure
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Segment(s) => Some(("segment", s.arrow, s.color)),
+            Item::Ray(r) => Some(("ray", r.arrow, r.color)),
+            _ => None,
+        })
+        .collect();
+
+    assert!(
+        arrows.contains(&("segment", true, Some(Color { r: 255, g: 0, b: 0 }))),
+        "{arrows:?}"
+    );
+    assert!(arrows.contains(&("ray", true, None)), "{arrows:?}");
+}
+
+#[test]
+fn render_every_format() {
+    let (glide, intermediate) = run(&fs::read_to_string(path("ccw")).unwrap());
+    let flags = Arc::new(intermediate.flags);
+
+    for format in Format::ALL {
+        assert_eq!(format.name().parse::<Format>().unwrap(), format);
+
+        let target = env::temp_dir()
+            .join("geo-aid-render-every-format")
+            .with_extension(format.extension());
+        let width = format.default_width();
+        format::render(format, &target, (width, width), glide.get_figure(), &flags).unwrap();
+        assert!(fs::metadata(&target).unwrap().len() > 0, "{format}");
+    }
+
+    assert!("bmp".parse::<Format>().is_err());
+}
@@ -1,7 +1,13 @@
 //! Geo-AID is capable of outputting figures in JSON, according to the schema at top level
-//! in the repo. This is the format writer responsible for this.
+//! in the repo. This is the format writer responsible for this. Figures written this way
+//! can also be loaded back, e.g. to render them in another format without regenerating.
+//!
+//! Every item and entity references expressions by their index, which is deterministic
+//! for a given script. This makes the IDs stable across regenerations.
 
-use geo_aid_figure::Figure;
+use std::fmt::{Display, Formatter};
+
+use geo_aid_figure::{Figure, SCHEMA_VERSION};
 
 /// The JSON format writer.
 #[derive(Default)]
@@ -13,6 +19,49 @@ impl Json {
     /// # Panics
     /// Panicking is a bug.
     pub fn draw(figure: &Figure) -> String {
-        serde_json::to_string(figure).unwrap()
+        let mut value = serde_json::to_value(figure).unwrap();
+        value["schema-version"] = SCHEMA_VERSION.into();
+
+        value.to_string()
+    }
+
+    /// Load a figure previously output with [`Json::draw`].
+    ///
+    /// # Errors
+    /// Returns an error if the input is not a valid figure or was written
+    /// with a different schema version.
+    pub fn load(json: &str) -> Result<Figure, LoadError> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(LoadError::Invalid)?;
+
+        let version = value["schema-version"].as_u64();
+        if version != Some(u64::from(SCHEMA_VERSION)) {
+            return Err(LoadError::UnsupportedVersion(version));
+        }
+
+        serde_json::from_value(value).map_err(LoadError::Invalid)
     }
 }
+
+/// An error while loading a JSON figure.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The input is not a valid figure.
+    Invalid(serde_json::Error),
+    /// The figure was written with another schema version (or none at all).
+    UnsupportedVersion(Option<u64>),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(err) => write!(f, "invalid figure: {err}"),
+            Self::UnsupportedVersion(Some(version)) => write!(
+                f,
+                "unsupported schema version {version}, expected {SCHEMA_VERSION}"
+            ),
+            Self::UnsupportedVersion(None) => write!(f, "missing schema version"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
@@ -1,8 +1,11 @@
 //! Geo-AID is capable of outputting its figures in LaTeX, using tikz and tikz-euclide packages.
 
-use geo_aid_figure::math_string::{MathChar, MathIndex, MathSpecial, MathString, SPECIAL_MATH};
+use geo_aid_figure::math_string::{
+    push_latex_escaped, MathChar, MathIndex, MathSpecial, MathString, SPECIAL_MATH,
+};
 use geo_aid_figure::{
-    CircleItem, Figure, Item, LineItem, PointItem, Position, Style, TwoPointItem,
+    AngleItem, CircleItem, Color, Figure, Item, Label, LineItem, PointItem, Position, Style,
+    TwoPointItem,
 };
 use num_traits::ToPrimitive;
 use std::string::String;
@@ -20,7 +23,7 @@ impl Latex {
     pub fn draw(figure: &Figure) -> String {
         let mut latex = Self::default();
 
-        latex.begin();
+        latex.begin(figure);
 
         for item in &figure.items {
             match item {
@@ -29,6 +32,7 @@ impl Latex {
                 Item::Ray(ray) => latex.draw_ray(ray),
                 Item::Segment(segment) => latex.draw_segment(segment),
                 Item::Circle(circle) => latex.draw_circle(circle),
+                Item::Angle(angle) => latex.draw_angle(angle),
             }
         }
 
@@ -38,12 +42,14 @@ impl Latex {
     }
 
     /// Convert the given math string into a LaTeX string.
+    /// Characters special to LaTeX are escaped, unless the string is raw.
     fn math_to_latex(math: &MathString) -> String {
         let mut s = String::new();
 
         for c in math.iter().copied() {
             match c {
-                MathChar::Ascii(c) => s.push(c),
+                MathChar::Ascii(c) if math.is_raw() => s.push(c),
+                MathChar::Ascii(c) => push_latex_escaped(&mut s, c),
                 MathChar::Special(special) => match special {
                     MathSpecial::Quote => s += "\"",
                     special => s += &format!("\\{}", SPECIAL_MATH[special.to_usize().unwrap()]),
@@ -71,34 +77,58 @@ impl Latex {
         }
     }
 
+    /// Get the tikz color of an item. Items without a color are black.
+    fn get_color(color: Option<Color>) -> String {
+        color.map_or_else(
+            || String::from("black"),
+            |color| {
+                format!(
+                    "{{rgb,255:red,{};green,{};blue,{}}}",
+                    color.r, color.g, color.b
+                )
+            },
+        )
+    }
+
     /// Draw a styled segment delimited by two points.
-    fn draw_simple_segment(&mut self, points: &(Position, Position), style: Style) {
+    fn draw_simple_segment(
+        &mut self,
+        points: &(Position, Position),
+        style: Style,
+        color: Option<Color>,
+        arrow: bool,
+    ) {
         self.content += &format!(
             r#"
                 \begin{{scope}}
                     \coordinate (A) at ({},{});
                     \coordinate (B) at ({},{});
-                    \tkzDrawSegment[{}](A,B)
+                    \tkzDrawSegment[{}, color={}{}](A,B)
                 \end{{scope}}
             "#,
             points.0.x,
             points.0.y,
             points.1.x,
             points.1.y,
-            Self::get_style_name(style)
+            Self::get_style_name(style),
+            Self::get_color(color),
+            if arrow { ", ->" } else { "" }
         );
     }
 
-    fn begin(&mut self) {
-        self.content = String::from(
+    fn begin(&mut self, figure: &Figure) {
+        // Lines and circles may reach past the canvas, hence the clip.
+        self.content = format!(
             r"
-                \documentclass{article}
-                \usepackage{tikz}
-                \usepackage{tkz-euclide}
-                \usetikzlibrary {angles,calc,quotes}
-                \begin{document}
-                \begin{tikzpicture}
+                \documentclass{{article}}
+                \usepackage{{tikz}}
+                \usepackage{{tkz-euclide}}
+                \usetikzlibrary {{angles,calc,quotes}}
+                \begin{{document}}
+                \begin{{tikzpicture}}
+                \clip (0,0) rectangle ({},{});
             ",
+            figure.width, figure.height
         );
     }
 
@@ -108,12 +138,22 @@ impl Latex {
 
         self.content += &format!(
             r#"
-                \coordinate ({}) at ({}, {}); \fill[black] ({}) circle (1pt);
+                \coordinate ({}) at ({}, {}); \fill[color={}] ({}) circle ({}pt);
             "#,
-            id, pos.x, pos.y, id
+            id,
+            pos.x,
+            pos.y,
+            Self::get_color(point.color),
+            id,
+            point.size
         );
 
-        if let Some(label) = &point.label {
+        self.draw_label(point.label.as_ref());
+    }
+
+    /// Draw an item's label, if it has one.
+    fn draw_label(&mut self, label: Option<&Label>) {
+        if let Some(label) = label {
             let label_pos = label.position;
 
             self.content += &format!(
@@ -128,102 +168,57 @@ impl Latex {
     }
 
     fn draw_line(&mut self, line: &LineItem) {
-        self.draw_simple_segment(&line.points, line.style);
+        self.draw_simple_segment(&line.points, line.style, line.color, false);
+        self.draw_label(line.label.as_ref());
     }
 
     fn draw_ray(&mut self, ray: &TwoPointItem) {
-        self.draw_simple_segment(&ray.points, ray.style);
+        self.draw_simple_segment(&ray.points, ray.style, ray.color, ray.arrow);
+        self.draw_label(ray.label.as_ref());
     }
 
     fn draw_segment(&mut self, segment: &TwoPointItem) {
-        self.draw_simple_segment(&segment.points, segment.style);
+        self.draw_simple_segment(&segment.points, segment.style, segment.color, segment.arrow);
+        self.draw_label(segment.label.as_ref());
     }
 
-    // fn draw_angle(&mut self, angle: &RenderedAngle) {
-    //     let no_arcs = String::from("l"); // Requires a change later! It has to be based on info from the script
-    //
-    //     self.content += &match &angle.expr.kind {
-    //         ScalarExpr::AnglePoint(AnglePoint { arm1, origin, arm2 }) => {
-    //             format!(
-    //                 r#"
-    //                 \begin{{scope}}
-    //                     \coordinate (A) at {};
-    //                     \coordinate (B) at {};
-    //                     \coordinate (C) at {};
-    //                         \tkzMarkAngle[size = 0.5,mark = none,arc={no_arcs},mkcolor = black, {}](A,B,C)
-    //                 \end{{scope}}
-    //                 "#,
-    //                 get_point_name(arm1, output, angle.points.0, self.scale),
-    //                 get_point_name(origin, output, angle.points.1, self.scale),
-    //                 get_point_name(arm2, output, angle.points.2, self.scale),
-    //                 styling(angle.style)
-    //             )
-    //         }
-    //         // There are hard coded values in \coordinate, it is intentional, every point has it's label marked by Rendered::Point sequence above
-    //         ScalarExpr::AngleLine(_) => {
-    //             format!(
-    //                 r#"
-    //                 \begin{{scope}}
-    //                     \coordinate (A) at ({}, {});
-    //                     \coordinate (B) at ({}, {});
-    //                     \coordinate (C) at ({}, {});
-    //                         \tkzMarkAngle[size = 2,mark = none,arc={no_arcs},mkcolor = black, {}](A,B,C)
-    //                 \end{{scope}}
-    //                 "#,
-    //                 angle.points.0.real,
-    //                 angle.points.0.imaginary,
-    //                 angle.points.1.real,
-    //                 angle.points.1.imaginary,
-    //                 angle.points.2.real,
-    //                 angle.points.2.imaginary,
-    //                 styling(angle.style)
-    //             )
-    //         }
-    //         _ => unreachable!(),
-    //     };
-    // }fn draw_angle(&mut self, angle: &RenderedAngle) {
-    //     let no_arcs = String::from("l"); // Requires a change later! It has to be based on info from the script
-    //
-    //     self.content += &match &angle.expr.kind {
-    //         ScalarExpr::AnglePoint(AnglePoint { arm1, origin, arm2 }) => {
-    //             format!(
-    //                 r#"
-    //                 \begin{{scope}}
-    //                     \coordinate (A) at {};
-    //                     \coordinate (B) at {};
-    //                     \coordinate (C) at {};
-    //                         \tkzMarkAngle[size = 0.5,mark = none,arc={no_arcs},mkcolor = black, {}](A,B,C)
-    //                 \end{{scope}}
-    //                 "#,
-    //                 get_point_name(arm1, output, angle.points.0, self.scale),
-    //                 get_point_name(origin, output, angle.points.1, self.scale),
-    //                 get_point_name(arm2, output, angle.points.2, self.scale),
-    //                 styling(angle.style)
-    //             )
-    //         }
-    //         // There are hard coded values in \coordinate, it is intentional, every point has it's label marked by Rendered::Point sequence above
-    //         ScalarExpr::AngleLine(_) => {
-    //             format!(
-    //                 r#"
-    //                 \begin{{scope}}
-    //                     \coordinate (A) at ({}, {});
-    //                     \coordinate (B) at ({}, {});
-    //                     \coordinate (C) at ({}, {});
-    //                         \tkzMarkAngle[size = 2,mark = none,arc={no_arcs},mkcolor = black, {}](A,B,C)
-    //                 \end{{scope}}
-    //                 "#,
-    //                 angle.points.0.real,
-    //                 angle.points.0.imaginary,
-    //                 angle.points.1.real,
-    //                 angle.points.1.imaginary,
-    //                 angle.points.2.real,
-    //                 angle.points.2.imaginary,
-    //                 styling(angle.style)
-    //             )
-    //         }
-    //         _ => unreachable!(),
-    //     };
-    // }
+    fn draw_angle(&mut self, angle: &AngleItem) {
+        self.draw_label(angle.label.as_ref());
+
+        // `\tkzMarkAngle` always goes counterclockwise, so a clockwise arc needs its arms swapped.
+        let (arm1, origin, arm2) = if angle.end >= angle.start {
+            angle.points
+        } else {
+            (angle.points.2, angle.points.1, angle.points.0)
+        };
+
+        let arc = match angle.arcs {
+            0 => return,
+            1 => "l",
+            2 => "ll",
+            _ => "lll",
+        };
+
+        let style = Self::get_style_name(angle.style);
+        let color = Self::get_color(angle.color);
+        let mark = if angle.is_right {
+            format!("\\tkzMarkRightAngle[size = 0.5, color={color}, {style}]")
+        } else {
+            format!("\\tkzMarkAngle[size = 0.5,mark = none,arc={arc},color={color}, {style}]")
+        };
+
+        self.content += &format!(
+            r#"
+                \begin{{scope}}
+                    \coordinate (A) at ({}, {});
+                    \coordinate (B) at ({}, {});
+                    \coordinate (C) at ({}, {});
+                        {mark}(A,B,C)
+                \end{{scope}}
+            "#,
+            arm1.x, arm1.y, origin.x, origin.y, arm2.x, arm2.y
+        );
+    }
 
     fn draw_circle(&mut self, circle: &CircleItem) {
         let pos1 = circle.center;
@@ -238,15 +233,17 @@ impl Latex {
             \begin{{scope}}
                 \coordinate (A) at ({}, {});
                 \coordinate (B) at ({}, {});
-                    \tkzDrawCircle[{}](A,B)
+                    \tkzDrawCircle[{}, color={}](A,B)
             \end{{scope}}
             "#,
             pos1.x,
             pos1.y,
             pos2.x,
             pos2.y,
-            Self::get_style_name(circle.style)
+            Self::get_style_name(circle.style),
+            Self::get_color(circle.color)
         );
+        self.draw_label(circle.label.as_ref());
     }
 
     fn end(&mut self) -> &str {
@@ -2,7 +2,8 @@
 //! This capability is currently very limited and largely untested, especially UX wise.
 
 use geo_aid_figure::{
-    CircleItem, Figure, Item, Label, LineItem, PointItem, Position, Style, TwoPointItem,
+    AngleItem, CircleItem, Color, Figure, Item, Label, LineItem, PointItem, Position, Style,
+    TwoPointItem,
 };
 
 /// The raw format writer
@@ -25,6 +26,7 @@ impl Plaintext {
                 Item::Ray(ray) => plain.draw_ray(ray),
                 Item::Segment(segment) => plain.draw_segment(segment),
                 Item::Circle(circle) => plain.draw_circle(circle),
+                Item::Angle(angle) => plain.draw_angle(angle),
             }
         }
 
@@ -43,23 +45,32 @@ impl Plaintext {
         }
     }
 
+    /// Get the description of an item's color. Empty for items without a color (black).
+    fn get_color_description(color: Option<Color>) -> String {
+        color
+            .map(|color| format!(" in {color}"))
+            .unwrap_or_default()
+    }
+
     /// Draw a styled segment delimited by two points.
     fn draw_simple_segment(
         &mut self,
         (p1, p2): (Position, Position),
         style: Style,
+        color: Option<Color>,
         label: Option<&Label>,
     ) {
         let label = label.map(|l| l.content.to_string()).unwrap_or_default();
 
         self.content += &format!(
-            "{} line \"{}\" from ({:.3}, {:.3}) to ({:.3}, {:.3})\n",
+            "{} line \"{}\" from ({:.3}, {:.3}) to ({:.3}, {:.3}){}\n",
             Self::get_style_name(style),
             label,
             p1.x,
             p1.y,
             p2.x,
-            p2.y
+            p2.y,
+            Self::get_color_description(color)
         );
     }
 
@@ -71,50 +82,63 @@ impl Plaintext {
             .unwrap_or_default();
 
         self.content += &format!(
-            "point \"{label}\" at ({:.3}, {:.3})\n",
-            point.position.x, point.position.y
+            "point \"{label}\" at ({:.3}, {:.3}){}\n",
+            point.position.x,
+            point.position.y,
+            Self::get_color_description(point.color)
         );
     }
 
     fn draw_line(&mut self, line: &LineItem) {
-        self.draw_simple_segment(line.points, line.style, line.label.as_ref());
+        self.draw_simple_segment(line.points, line.style, line.color, line.label.as_ref());
     }
 
     fn draw_ray(&mut self, ray: &TwoPointItem) {
-        self.draw_simple_segment(ray.points, ray.style, ray.label.as_ref());
+        self.draw_simple_segment(ray.points, ray.style, ray.color, ray.label.as_ref());
     }
 
     fn draw_segment(&mut self, segment: &TwoPointItem) {
-        self.draw_simple_segment(segment.points, segment.style, segment.label.as_ref());
+        self.draw_simple_segment(
+            segment.points,
+            segment.style,
+            segment.color,
+            segment.label.as_ref(),
+        );
     }
 
-    // fn draw_angle(&mut self, angle: &RenderedAngle, output: &Output) {
-    //     let p_1 = angle.points.0;
-    //     let p_origin = angle.points.1;
-    //     let p_2 = angle.points.2;
-    //     let no_arcs = String::from("l"); // Requires a change later! It has to be based on info from the script
-    //     match &angle.expr.kind {
-    //         ScalarExpr::AnglePoint(AnglePoint { arm1, origin, arm2 }) => {
-    //             self.content += &format!("\n3 points angle: points' coordinates point1 - {}, origin - {}, point2 - {}, number of arcs: {no_arcs}, mode: {} \n",
-    //                 get_point_name(arm1, output, p_1), get_point_name(origin, output, p_origin), get_point_name(arm2, output, p_2), styling(angle.style)
-    //             );
-    //         }
-    //         ScalarExpr::AngleLine(_) => {
-    //             self.content += &format!("\n2 lines angle: points' coordinates: point1 - ({}, {}), origin - ({}, {}), point2 - ({}, {}), number of arcs: {no_arcs}, mode: {} \n",
-    //                 p_1.real, p_1. imaginary, p_origin.real, p_origin.imaginary, p_2.real, p_2.imaginary, styling(angle.style)
-    //             );
-    //         }
-    //         _ => unreachable!(),
-    //     }
-    // }
+    fn draw_angle(&mut self, angle: &AngleItem) {
+        let label = angle
+            .label
+            .as_ref()
+            .map(|x| format!("\"{}\"", x.content))
+            .unwrap_or_default();
+
+        let kind = if angle.is_right {
+            "right angle"
+        } else {
+            "angle"
+        };
+
+        self.content += &format!(
+            "{} {kind} \"{label}\" at ({:.3}, {:.3}) from {:.3}° to {:.3}° with {} arc(s){}\n",
+            Self::get_style_name(angle.style),
+            angle.points.1.x,
+            angle.points.1.y,
+            angle.start.to_degrees(),
+            angle.end.to_degrees(),
+            angle.arcs,
+            Self::get_color_description(angle.color),
+        );
+    }
 
     fn draw_circle(&mut self, circle: &CircleItem) {
         self.content += &format!(
-            "{} circle at ({:.3}, {:.3}) with radius {:.3}\n",
+            "{} circle at ({:.3}, {:.3}) with radius {:.3}{}\n",
             Self::get_style_name(circle.style),
             circle.center.x,
             circle.center.y,
             circle.radius,
+            Self::get_color_description(circle.color),
         );
     }
 }
@@ -0,0 +1,447 @@
+//! Geo-AID is capable of outputting figures as a PNG image. The image is rasterized in software,
+//! so viewing it doesn't require a LaTeX toolchain or an SVG renderer.
+//! Labels are typeset with a bundled DejaVu Serif font.
+
+use std::f64::consts::PI;
+
+use ab_glyph::{Font, FontRef, OutlineCurve};
+use geo_aid_figure::math_string::{MathChar, MathIndex, MathSpecial, MathString, GREEK};
+use geo_aid_figure::{
+    AngleItem, CircleItem, Color, Figure, Item, Label, LineItem, PointItem, Position, Style,
+    TwoPointItem, ANGLE_ARC_RADIUS, ANGLE_ARC_SPACING, RIGHT_ANGLE_MARK_SIZE,
+};
+use tiny_skia::{FillRule, Paint, Path, PathBuilder, Pixmap, Stroke, StrokeDash, Transform};
+
+/// The number of segments used to approximate a full circle's worth of an arc.
+const ARC_RESOLUTION: f64 = 64.0;
+
+/// The length of an arrowhead's sides.
+const ARROWHEAD_SIZE: f64 = 8.0;
+
+/// The angle (in radians) between an arrowhead's side and its shaft.
+const ARROWHEAD_ANGLE: f64 = PI / 8.0;
+
+/// Label font size as a fraction of the smaller canvas dimension.
+const LABEL_FONT_SCALE: f64 = 0.025;
+
+/// The size of lower indices relative to the label's font size.
+const INDEX_SCALE: f64 = 0.7;
+
+/// How far below the baseline lower indices are drawn, relative to the label's font size.
+const INDEX_SHIFT: f64 = 0.3;
+
+/// The font used for upright label characters.
+const FONT_REGULAR: &[u8] = include_bytes!("../fonts/DejaVuSerif.ttf");
+
+/// The font used for letters in labels.
+const FONT_ITALIC: &[u8] = include_bytes!("../fonts/DejaVuSerif-Italic.ttf");
+
+/// A single character of a label, ready to be typeset.
+struct LabelGlyph {
+    /// The character
+    c: char,
+    /// Whether to use the italic font
+    italic: bool,
+    /// Whether the character is a part of a lower index
+    lower: bool,
+}
+
+/// The PNG format writer.
+#[derive(Debug)]
+pub struct Png {
+    /// The image being drawn
+    pixmap: Pixmap,
+    /// Maps figure space (y axis pointing up) onto the image (y axis pointing down).
+    transform: Transform,
+    /// Canvas width
+    width: f64,
+    /// Canvas height
+    height: f64,
+    /// Label font size
+    font_size: f64,
+    /// The font used for upright label characters
+    regular: FontRef<'static>,
+    /// The font used for letters in labels
+    italic: FontRef<'static>,
+}
+
+impl Png {
+    /// Get the figure in PNG format.
+    ///
+    /// # Panics
+    /// If the figure is empty or too big to be allocated.
+    #[must_use]
+    pub fn draw(figure: &Figure) -> Vec<u8> {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let mut pixmap =
+            Pixmap::new(figure.width.ceil() as u32, figure.height.ceil() as u32).unwrap();
+        pixmap.fill(tiny_skia::Color::WHITE);
+
+        let mut png = Self {
+            pixmap,
+            transform: Transform::from_row(1.0, 0.0, 0.0, -1.0, 0.0, figure.height as f32),
+            width: figure.width,
+            height: figure.height,
+            font_size: f64::min(figure.width, figure.height) * LABEL_FONT_SCALE,
+            regular: FontRef::try_from_slice(FONT_REGULAR).unwrap(),
+            italic: FontRef::try_from_slice(FONT_ITALIC).unwrap(),
+        };
+
+        for item in &figure.items {
+            match item {
+                Item::Point(point) => png.draw_point(point),
+                Item::Line(line) => png.draw_line(line),
+                Item::Ray(ray) => png.draw_ray(ray),
+                Item::Segment(segment) => png.draw_segment(segment),
+                Item::Circle(circle) => png.draw_circle(circle),
+                Item::Angle(angle) => png.draw_angle(angle),
+            }
+        }
+
+        png.pixmap.encode_png().unwrap()
+    }
+}
+
+impl Png {
+    /// Get the paint used to draw an item. Items without a color are black.
+    fn get_paint(color: Option<Color>) -> Paint<'static> {
+        let color = color.unwrap_or(Color { r: 0, g: 0, b: 0 });
+
+        let mut paint = Paint::default();
+        paint.set_color_rgba8(color.r, color.g, color.b, 255);
+        paint.anti_alias = true;
+
+        paint
+    }
+
+    /// Get the stroke of a line made with the given [`Style`]
+    fn get_stroke(style: Style) -> Stroke {
+        let (width, dash) = match style {
+            Style::Dotted => (0.5, Some(vec![0.8, 1.0])),
+            Style::Dashed => (0.5, Some(vec![2.0, 2.0])),
+            Style::Bold => (2.0, None),
+            Style::Solid => (1.0, None),
+        };
+
+        Stroke {
+            width,
+            dash: dash.and_then(|dash| StrokeDash::new(dash, 0.0)),
+            ..Stroke::default()
+        }
+    }
+
+    /// Stroke the given path.
+    fn stroke(&mut self, path: Option<Path>, style: Style, color: Option<Color>) {
+        if let Some(path) = path {
+            self.pixmap.stroke_path(
+                &path,
+                &Self::get_paint(color),
+                &Self::get_stroke(style),
+                self.transform,
+                None,
+            );
+        }
+    }
+
+    /// Draw a styled polyline going through the given points.
+    fn draw_polyline(
+        &mut self,
+        points: impl IntoIterator<Item = Position>,
+        style: Style,
+        color: Option<Color>,
+    ) {
+        let mut builder = PathBuilder::new();
+        let mut points = points.into_iter();
+
+        if let Some(first) = points.next() {
+            builder.move_to(first.x as f32, first.y as f32);
+        }
+
+        for point in points {
+            builder.line_to(point.x as f32, point.y as f32);
+        }
+
+        self.stroke(builder.finish(), style, color);
+    }
+
+    /// Split a math string into typeset characters.
+    fn math_to_glyphs(math: &MathString) -> Vec<LabelGlyph> {
+        let mut glyphs = Vec::new();
+        let mut lower = false;
+
+        for c in math.iter().copied() {
+            let (c, italic) = match c {
+                MathChar::Ascii(c) => (c, c.is_ascii_alphabetic()),
+                MathChar::Special(MathSpecial::Quote) => ('"', false),
+                MathChar::Special(special) => (GREEK[special as usize], true),
+                MathChar::SetIndex(index) => {
+                    lower = index == MathIndex::Lower;
+                    continue;
+                }
+                MathChar::Prime => ('′', false),
+            };
+
+            glyphs.push(LabelGlyph { c, italic, lower });
+        }
+
+        glyphs
+    }
+
+    /// Draw a label centered at its position.
+    fn draw_label(&mut self, label: Option<&Label>) {
+        let Some(label) = label else {
+            return;
+        };
+
+        let glyphs = Self::math_to_glyphs(&label.content);
+        let font = |glyph: &LabelGlyph| {
+            if glyph.italic {
+                &self.italic
+            } else {
+                &self.regular
+            }
+        };
+        // Font units to figure space.
+        let scale = |glyph: &LabelGlyph| {
+            let size = if glyph.lower {
+                self.font_size * INDEX_SCALE
+            } else {
+                self.font_size
+            };
+
+            size / f64::from(font(glyph).height_unscaled())
+        };
+        let advance = |glyph: &LabelGlyph| {
+            let font = font(glyph);
+            f64::from(font.h_advance_unscaled(font.glyph_id(glyph.c))) * scale(glyph)
+        };
+
+        let width: f64 = glyphs.iter().map(advance).sum();
+        let half_height = self.font_size / 2.0;
+        let x = label
+            .position
+            .x
+            .clamp(width / 2.0, (self.width - width / 2.0).max(width / 2.0));
+        let y = label
+            .position
+            .y
+            .clamp(half_height, (self.height - half_height).max(half_height));
+
+        // The baseline is placed so that the font's line is vertically centered.
+        let ascent = f64::from(self.regular.ascent_unscaled());
+        let descent = f64::from(self.regular.descent_unscaled());
+        let baseline = y - (ascent + descent) / 2.0 * self.font_size / (ascent - descent);
+
+        let mut builder = PathBuilder::new();
+        let mut pen = x - width / 2.0;
+
+        for glyph in &glyphs {
+            let font = font(glyph);
+            let scale = scale(glyph);
+            let origin = Position {
+                x: pen,
+                y: if glyph.lower {
+                    baseline - self.font_size * INDEX_SHIFT
+                } else {
+                    baseline
+                },
+            };
+            let to_figure = |p: ab_glyph::Point| {
+                let p = origin
+                    + Position {
+                        x: f64::from(p.x),
+                        y: f64::from(p.y),
+                    } * scale;
+                (p.x as f32, p.y as f32)
+            };
+
+            if let Some(outline) = font.outline(font.glyph_id(glyph.c)) {
+                // Consecutive curves share their ends unless a new contour starts.
+                let mut last = None;
+
+                for curve in outline.curves {
+                    let (start, end) = match curve {
+                        OutlineCurve::Line(a, b)
+                        | OutlineCurve::Quad(a, _, b)
+                        | OutlineCurve::Cubic(a, _, _, b) => (a, b),
+                    };
+
+                    if last != Some(start) {
+                        if last.is_some() {
+                            builder.close();
+                        }
+                        let (x, y) = to_figure(start);
+                        builder.move_to(x, y);
+                    }
+
+                    match curve {
+                        OutlineCurve::Line(_, b) => {
+                            let (x, y) = to_figure(b);
+                            builder.line_to(x, y);
+                        }
+                        OutlineCurve::Quad(_, c, b) => {
+                            let (cx, cy) = to_figure(c);
+                            let (x, y) = to_figure(b);
+                            builder.quad_to(cx, cy, x, y);
+                        }
+                        OutlineCurve::Cubic(_, c1, c2, b) => {
+                            let (c1x, c1y) = to_figure(c1);
+                            let (c2x, c2y) = to_figure(c2);
+                            let (x, y) = to_figure(b);
+                            builder.cubic_to(c1x, c1y, c2x, c2y, x, y);
+                        }
+                    }
+
+                    last = Some(end);
+                }
+
+                if last.is_some() {
+                    builder.close();
+                }
+            }
+
+            pen += advance(glyph);
+        }
+
+        if let Some(path) = builder.finish() {
+            self.pixmap.fill_path(
+                &path,
+                &Self::get_paint(None),
+                FillRule::Winding,
+                self.transform,
+                None,
+            );
+        }
+    }
+
+    /// Draw a filled arrowhead at `to`, pointing away from `from`.
+    fn draw_arrowhead(&mut self, from: Position, to: Position, color: Option<Color>) {
+        let theta = (to.y - from.y).atan2(to.x - from.x);
+        let side = |phi: f64| {
+            to + Position {
+                x: phi.cos(),
+                y: phi.sin(),
+            } * ARROWHEAD_SIZE
+        };
+        let left = side(theta + PI - ARROWHEAD_ANGLE);
+        let right = side(theta + PI + ARROWHEAD_ANGLE);
+
+        let mut builder = PathBuilder::new();
+        builder.move_to(to.x as f32, to.y as f32);
+        builder.line_to(left.x as f32, left.y as f32);
+        builder.line_to(right.x as f32, right.y as f32);
+        builder.close();
+
+        if let Some(path) = builder.finish() {
+            self.pixmap.fill_path(
+                &path,
+                &Self::get_paint(color),
+                FillRule::Winding,
+                self.transform,
+                None,
+            );
+        }
+    }
+
+    fn draw_point(&mut self, point: &PointItem) {
+        let pos = point.position;
+
+        if let Some(path) = PathBuilder::from_circle(pos.x as f32, pos.y as f32, point.size as f32)
+        {
+            self.pixmap.fill_path(
+                &path,
+                &Self::get_paint(point.color),
+                FillRule::Winding,
+                self.transform,
+                None,
+            );
+        }
+
+        self.draw_label(point.label.as_ref());
+    }
+
+    fn draw_line(&mut self, line: &LineItem) {
+        self.draw_polyline([line.points.0, line.points.1], line.style, line.color);
+        self.draw_label(line.label.as_ref());
+    }
+
+    fn draw_ray(&mut self, ray: &TwoPointItem) {
+        self.draw_polyline([ray.points.0, ray.points.1], ray.style, ray.color);
+
+        if ray.arrow {
+            self.draw_arrowhead(ray.points.0, ray.points.1, ray.color);
+        }
+
+        self.draw_label(ray.label.as_ref());
+    }
+
+    fn draw_segment(&mut self, segment: &TwoPointItem) {
+        self.draw_polyline(
+            [segment.points.0, segment.points.1],
+            segment.style,
+            segment.color,
+        );
+
+        if segment.arrow {
+            self.draw_arrowhead(segment.points.0, segment.points.1, segment.color);
+        }
+
+        self.draw_label(segment.label.as_ref());
+    }
+
+    fn draw_angle(&mut self, angle: &AngleItem) {
+        self.draw_label(angle.label.as_ref());
+
+        if angle.arcs == 0 {
+            return;
+        }
+
+        let origin = angle.points.1;
+        let direction = |theta: f64| Position {
+            x: theta.cos(),
+            y: theta.sin(),
+        };
+
+        if angle.is_right {
+            let arm1 = direction(angle.start) * RIGHT_ANGLE_MARK_SIZE;
+            let arm2 = direction(angle.end) * RIGHT_ANGLE_MARK_SIZE;
+
+            self.draw_polyline(
+                [origin + arm1, origin + arm1 + arm2, origin + arm2],
+                angle.style,
+                angle.color,
+            );
+            return;
+        }
+
+        // Arcs are approximated with segments.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let steps = ((angle.end - angle.start).abs() / (2.0 * PI) * ARC_RESOLUTION)
+            .ceil()
+            .max(1.0) as u32;
+
+        for i in 0..angle.arcs {
+            let radius = ANGLE_ARC_RADIUS + f64::from(i) * ANGLE_ARC_SPACING;
+
+            self.draw_polyline(
+                (0..=steps).map(|step| {
+                    let t = f64::from(step) / f64::from(steps);
+                    origin + direction(angle.start + (angle.end - angle.start) * t) * radius
+                }),
+                angle.style,
+                angle.color,
+            );
+        }
+    }
+
+    fn draw_circle(&mut self, circle: &CircleItem) {
+        let path = PathBuilder::from_circle(
+            circle.center.x as f32,
+            circle.center.y as f32,
+            circle.radius as f32,
+        );
+
+        self.stroke(path, circle.style, circle.color);
+        self.draw_label(circle.label.as_ref());
+    }
+}
@@ -8,7 +8,7 @@ use crate::math::{EntityKind, IndexMap, Reconstruct, ReconstructCtx, Reindex};
 use geo_aid_figure::math_string::{
     MathChar, MathIndex, MathSpecial, MathString, ParseErrorKind, SPECIAL_MATH,
 };
-use geo_aid_figure::{Style, VarIndex};
+use geo_aid_figure::{Color, Style, VarIndex};
 
 use crate::span;
 
@@ -30,6 +30,10 @@ pub struct PointItem {
     pub label: MathString,
     /// Whether to display a small circle in its place
     pub display_dot: bool,
+    /// The dot's size, relative to the default
+    pub size: f64,
+    /// The point's color
+    pub color: Option<Color>,
 }
 
 impl Reindex for PointItem {
@@ -62,6 +66,8 @@ pub struct CircleItem {
     pub label: MathString,
     /// How to draw the circle (brush)
     pub style: Style,
+    /// The circle's color
+    pub color: Option<Color>,
 }
 
 impl Reindex for CircleItem {
@@ -94,6 +100,8 @@ pub struct LineItem {
     pub label: MathString,
     /// How to draw the line (brush)
     pub style: Style,
+    /// The line's color
+    pub color: Option<Color>,
 }
 
 impl Reindex for LineItem {
@@ -128,6 +136,10 @@ pub struct RayItem {
     pub label: MathString,
     /// How to draw the ray (brush)
     pub style: Style,
+    /// The ray's color
+    pub color: Option<Color>,
+    /// Whether to draw an arrowhead at the ray's far end
+    pub arrow: bool,
 }
 
 impl Reindex for RayItem {
@@ -164,6 +176,10 @@ pub struct SegmentItem {
     pub label: MathString,
     /// How to draw the segment (brush)
     pub style: Style,
+    /// The segment's color
+    pub color: Option<Color>,
+    /// Whether to draw an arrowhead at the second endpoint
+    pub arrow: bool,
 }
 
 impl From<SegmentItem> for Item {
@@ -189,6 +205,85 @@ impl Reconstruct for SegmentItem {
     }
 }
 
+/// The way a drawn angle is defined
+#[derive(Debug, Clone)]
+pub enum AngleDefinition {
+    /// Three points: the first arm's point, the origin and the second arm's point.
+    ThreePoint(VarIndex, VarIndex, VarIndex),
+    /// Two lines. The origin is their intersection.
+    TwoLine(VarIndex, VarIndex),
+}
+
+impl Reindex for AngleDefinition {
+    fn reindex(&mut self, map: &IndexMap) {
+        match self {
+            Self::ThreePoint(p, q, r) => {
+                p.reindex(map);
+                q.reindex(map);
+                r.reindex(map);
+            }
+            Self::TwoLine(k, l) => {
+                k.reindex(map);
+                l.reindex(map);
+            }
+        }
+    }
+}
+
+impl Reconstruct for AngleDefinition {
+    fn reconstruct(self, ctx: &mut ReconstructCtx) -> Self {
+        match self {
+            Self::ThreePoint(p, q, r) => {
+                Self::ThreePoint(p.reconstruct(ctx), q.reconstruct(ctx), r.reconstruct(ctx))
+            }
+            Self::TwoLine(k, l) => Self::TwoLine(k.reconstruct(ctx), l.reconstruct(ctx)),
+        }
+    }
+}
+
+/// A drawn angle
+#[derive(Debug, Clone)]
+pub struct AngleItem {
+    /// Index of the defining expression
+    pub id: VarIndex,
+    /// Indices of the expressions delimiting the angle
+    pub definition: AngleDefinition,
+    /// Whether the angle is directed. Directed angles are always marked
+    /// counterclockwise from the first arm.
+    pub directed: bool,
+    /// The number of arcs marking the angle
+    pub arcs: u8,
+    /// The angle's label
+    pub label: MathString,
+    /// How to draw the arc (brush)
+    pub style: Style,
+    /// The arc's color
+    pub color: Option<Color>,
+}
+
+impl From<AngleItem> for Item {
+    fn from(value: AngleItem) -> Self {
+        Self::Angle(value)
+    }
+}
+
+impl Reindex for AngleItem {
+    fn reindex(&mut self, map: &IndexMap) {
+        self.id.reindex(map);
+        self.definition.reindex(map);
+    }
+}
+
+impl Reconstruct for AngleItem {
+    fn reconstruct(self, ctx: &mut ReconstructCtx) -> Self {
+        Self {
+            id: self.id.reconstruct(ctx),
+            definition: self.definition.reconstruct(ctx),
+            ..self
+        }
+    }
+}
+
 /// A type-erased drawn item of the figure
 #[derive(Debug, Clone)]
 pub enum Item {
@@ -197,6 +292,7 @@ pub enum Item {
     Line(LineItem),
     Ray(RayItem),
     Segment(SegmentItem),
+    Angle(AngleItem),
 }
 
 impl Reindex for Item {
@@ -207,6 +303,7 @@ impl Reindex for Item {
             Self::Line(v) => v.reindex(map),
             Self::Ray(v) => v.reindex(map),
             Self::Segment(v) => v.reindex(map),
+            Self::Angle(v) => v.reindex(map),
         }
     }
 }
@@ -219,6 +316,7 @@ impl Reconstruct for Item {
             Self::Line(v) => Self::Line(v.reconstruct(ctx)),
             Self::Ray(v) => Self::Ray(v.reconstruct(ctx)),
             Self::Segment(v) => Self::Segment(v.reconstruct(ctx)),
+            Self::Angle(v) => Self::Angle(v.reconstruct(ctx)),
         }
     }
 }
@@ -409,13 +507,14 @@ impl From<PointCollectionItem> for SpannedMathString {
     fn from(value: PointCollectionItem) -> Self {
         let mut string = MathString::new();
         string.push(MathChar::Ascii(value.letter));
+        string.extend([MathChar::Prime].repeat(value.primes.into()));
 
         if let Some(index) = value.index {
+            string.push(MathChar::SetIndex(MathIndex::Lower));
             string.extend(index.chars().map(MathChar::Ascii));
+            string.push(MathChar::SetIndex(MathIndex::Normal));
         }
 
-        string.extend([MathChar::Prime].repeat(value.primes.into()));
-
         Self {
             string,
             span: value.span,
@@ -14,6 +14,7 @@ use serde::Serialize;
 
 use self::parser::Type;
 use self::token::{number::CompExponent, NamedIdent, Span, Token};
+use self::unroll::figure::COLOR_NAMES;
 
 pub mod cli;
 pub mod figure;
@@ -177,6 +178,15 @@ pub enum Error {
         params: Vec<Type>,
         /// The function name
         function_name: String,
+        /// Signatures of the function's overloads
+        candidates: Vec<String>,
+    },
+    /// A square root of a scalar whose unit has an exponent that isn't even.
+    OddUnitExponent {
+        /// The call span
+        error_span: Span,
+        /// The unit of the scalar
+        unit: ComplexUnit,
     },
     /// Cannot unpack a type onto a point collection.
     CannotUnpack {
@@ -254,6 +264,8 @@ pub enum Error {
     NumberExpected { error_span: Span },
     /// The provided identifier cannot be converted into a math string.
     InvalidIdentMathString { error_span: Span },
+    /// The provided value is not a valid color.
+    InvalidColor { error_span: Span },
     /// A flag's value was set more than once
     RedefinedFlag {
         /// The exact error span
@@ -307,6 +319,8 @@ pub enum Error {
     },
     /// There's an unclosed string
     UnclosedString { error_span: Span },
+    /// There's an unclosed block comment
+    UnclosedComment { error_span: Span },
     /// An index was found inside another index, in a math string
     LabelIndexInsideIndex { error_span: Span },
     /// An unexpected property was found
@@ -332,6 +346,15 @@ pub enum Error {
     ZeroDenominator { error_span: Span },
     /// A function name was expected
     ExpectedFunction { error_span: Span },
+    /// A point collection has too few points.
+    NotEnoughPoints {
+        /// The collection's span
+        error_span: Span,
+        /// The minimal number of points
+        at_least: usize,
+        /// The number of points received
+        got: usize,
+    },
 }
 
 impl Error {
@@ -484,10 +507,18 @@ impl Error {
                 error_span,
                 params,
                 function_name,
+                candidates,
             } => {
+                let available = candidates.iter().map(|x| format!("\n    {function_name}{x}")).collect::<String>();
                 DiagnosticData::new(&format!("overload for function `{function_name}` with params `({})` not found", params.into_iter().map(|x| format!("{x}")).collect::<Vec<String>>().join(", ")))
                     .add_span(error_span)
+                    .add_note(AnnotationKind::Note, format!("available overloads:{available}"))
             },
+            Self::OddUnitExponent { error_span, unit } => {
+                DiagnosticData::new(&format!("cannot take the square root of a value of unit `{unit}`"))
+                    .add_span(error_span)
+                    .add_note(AnnotationKind::Note, String::from("all exponents of the unit must be even"))
+            }
             Self::CannotUnpack { error_span, ty } => {
                 DiagnosticData::new(&format!("could not unpack `{ty}` onto a point collection"))
                     .add_span(error_span)
@@ -577,6 +608,14 @@ impl Error {
                 DiagnosticData::new(&"invalid ident for a math string")
                     .add_span(error_span)
             }
+            Self::InvalidColor { error_span } => {
+                DiagnosticData::new(&"invalid color")
+                    .add_span(error_span)
+                    .add_annotation(error_span, AnnotationKind::Help, &format!(
+                        "expected a hex code, like \"#ff8000\", or one of: {}",
+                        COLOR_NAMES.iter().map(|v| format!("`{v}`")).collect::<Vec<String>>().join(", ")
+                    ))
+            }
             Self::RedefinedFlag {
                 first_defined,
                 error_span,
@@ -659,6 +698,10 @@ impl Error {
                 DiagnosticData::new(&"unclosed special tag")
                     .add_span(error_span)
             }
+            Self::UnclosedComment { error_span } => {
+                DiagnosticData::new(&"unclosed block comment")
+                    .add_span(error_span)
+            }
             Self::LabelIndexInsideIndex { error_span } => {
                 DiagnosticData::new(&"lower index cannot be used inside another lower index")
                     .add_span(error_span)
@@ -686,6 +729,10 @@ impl Error {
                 DiagnosticData::new(&"expected function, found, value")
                     .add_span(error_span)
             }
+            Self::NotEnoughPoints { error_span, at_least, got } => {
+                DiagnosticData::new(&format!("expected at least {at_least} points, got {got}"))
+                    .add_span(error_span)
+            }
         }
     }
 }
@@ -22,7 +22,7 @@ use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 
-use self::optimizations::{EqExpressions, EqPointDst, RightAngle};
+use self::optimizations::{EqExpressions, EqPointDst, RightAngle, SolveDistanceUnit};
 
 use super::unroll::GetData;
 use super::{
@@ -41,6 +41,16 @@ mod optimizations;
 #[derive(Debug, Clone)]
 pub struct Optimizations {}
 
+/// How distance literals are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistanceLiterals {
+    /// The distance unit is an adjustable, like any free real.
+    #[default]
+    Adjust,
+    /// The distance unit is solved for from a rule it appears in, if there is a suitable one.
+    Solve,
+}
+
 /// Compiler flags.
 #[derive(Debug, Clone)]
 pub struct Flags {
@@ -48,6 +58,10 @@ pub struct Flags {
     pub optimizations: Optimizations,
     /// Whether to include point inequalitiy rules.
     pub point_inequalities: bool,
+    /// Whether to scale point inequality weights with the number of points.
+    pub scale_point_inequalities: bool,
+    /// How distance literals are handled.
+    pub distance_literals: DistanceLiterals,
 }
 
 impl Default for Flags {
@@ -55,6 +69,8 @@ impl Default for Flags {
         Self {
             optimizations: Optimizations {},
             point_inequalities: false,
+            scale_point_inequalities: false,
+            distance_literals: DistanceLiterals::default(),
         }
     }
 }
@@ -408,12 +424,15 @@ pub enum ExprKind {
     AveragePoint { items: Vec<VarIndex> },
     /// Center of a circle.
     CircleCenter { circle: VarIndex },
+    /// A point rotated about the origin by an angle.
+    Rotate { point: VarIndex, angle: VarIndex },
 
     // NUMBER
     /// Sum of numbers.
     ///
     /// A normalized sum must be sorted and must not contain other sums or negations.
-    /// An aggregated constant, if any, must be at the end.
+    /// An aggregated constant, if any, must be at the end. A sum of constants only
+    /// is folded into a single [`ExprKind::Const`].
     Sum {
         /// Items to add.
         plus: Vec<VarIndex>,
@@ -423,7 +442,8 @@ pub enum ExprKind {
     /// Product of numbers.
     ///
     /// A normalized product must be sorted and must not contain other products.
-    /// An aggregated constant, if any, must be at the end.
+    /// An aggregated constant, if any, must be at the end. A product of constants only
+    /// is folded into a single [`ExprKind::Const`].
     Product {
         /// Items to multiply by.
         times: Vec<VarIndex>,
@@ -466,6 +486,14 @@ pub enum ExprKind {
     PointX { point: VarIndex },
     /// The imaginary part of a point.
     PointY { point: VarIndex },
+    /// Sine of an angle.
+    Sin { angle: VarIndex },
+    /// Cosine of an angle.
+    Cos { angle: VarIndex },
+    /// Tangent of an angle.
+    Tan { angle: VarIndex },
+    /// Absolute value of a number.
+    Abs { value: VarIndex },
 
     // Line
     /// A line through two points.
@@ -495,22 +523,27 @@ impl ExprKind {
             Self::LineLineIntersection { .. } => 1,
             Self::AveragePoint { .. } => 2,
             Self::CircleCenter { .. } => 3,
-            Self::Sum { .. } => 4,
-            Self::Product { .. } => 5,
-            Self::Const { .. } => 6,
-            Self::PartialPower { .. } => 7,
-            Self::PointPointDistance { .. } => 8,
-            Self::PointLineDistance { .. } => 9,
-            Self::ThreePointAngle { .. } => 10,
-            Self::ThreePointAngleDir { .. } => 11,
-            Self::TwoLineAngle { .. } => 12,
-            Self::PointX { .. } => 13,
-            Self::PointY { .. } => 14,
-            Self::PointPoint { .. } => 15,
-            Self::AngleBisector { .. } => 16,
-            Self::ParallelThrough { .. } => 17,
-            Self::PerpendicularThrough { .. } => 18,
-            Self::ConstructCircle { .. } => 19,
+            Self::Rotate { .. } => 4,
+            Self::Sum { .. } => 5,
+            Self::Product { .. } => 6,
+            Self::Const { .. } => 7,
+            Self::PartialPower { .. } => 8,
+            Self::PointPointDistance { .. } => 9,
+            Self::PointLineDistance { .. } => 10,
+            Self::ThreePointAngle { .. } => 11,
+            Self::ThreePointAngleDir { .. } => 12,
+            Self::TwoLineAngle { .. } => 13,
+            Self::PointX { .. } => 14,
+            Self::PointY { .. } => 15,
+            Self::Sin { .. } => 16,
+            Self::Cos { .. } => 17,
+            Self::Tan { .. } => 18,
+            Self::Abs { .. } => 19,
+            Self::PointPoint { .. } => 20,
+            Self::AngleBisector { .. } => 21,
+            Self::ParallelThrough { .. } => 22,
+            Self::PerpendicularThrough { .. } => 23,
+            Self::ConstructCircle { .. } => 24,
         }
     }
 
@@ -603,6 +636,16 @@ impl ExprKind {
                         center: other_a,
                         radius: other_b,
                     },
+                )
+                | (
+                    Self::Rotate {
+                        point: self_a,
+                        angle: self_b,
+                    },
+                    Self::Rotate {
+                        point: other_a,
+                        angle: other_b,
+                    },
                 ) => self_a
                     .compare(other_a, math)
                     .then_with(|| self_b.compare(other_b, math)),
@@ -612,7 +655,11 @@ impl ExprKind {
                 ) => self_items.compare(other_items, math),
                 (Self::CircleCenter { circle: self_x }, Self::CircleCenter { circle: other_x })
                 | (Self::PointX { point: self_x }, Self::PointX { point: other_x })
-                | (Self::PointY { point: self_x }, Self::PointY { point: other_x }) => {
+                | (Self::PointY { point: self_x }, Self::PointY { point: other_x })
+                | (Self::Sin { angle: self_x }, Self::Sin { angle: other_x })
+                | (Self::Cos { angle: self_x }, Self::Cos { angle: other_x })
+                | (Self::Tan { angle: self_x }, Self::Tan { angle: other_x })
+                | (Self::Abs { value: self_x }, Self::Abs { value: other_x }) => {
                     self_x.compare(other_x, math)
                 }
                 (
@@ -702,10 +749,17 @@ impl ExprKind {
             Self::Entity { id } => entities[id.0].get_type(expressions, entities),
             Self::LineLineIntersection { .. }
             | Self::AveragePoint { .. }
-            | Self::CircleCenter { .. } => ExprType::Point,
-            Self::Sum { .. }
-            | Self::Product { .. }
-            | Self::Const { .. }
+            | Self::CircleCenter { .. }
+            | Self::Rotate { .. } => ExprType::Point,
+            // Points can be added and scaled as complex numbers.
+            Self::Sum { plus: items, .. } | Self::Product { times: items, .. } => {
+                if items.iter().any(|v| expressions[v.0].ty == ExprType::Point) {
+                    ExprType::Point
+                } else {
+                    ExprType::Number
+                }
+            }
+            Self::Const { .. }
             | Self::PartialPower { .. }
             | Self::PointPointDistance { .. }
             | Self::PointLineDistance { .. }
@@ -713,7 +767,11 @@ impl ExprKind {
             | Self::ThreePointAngleDir { .. }
             | Self::TwoLineAngle { .. }
             | Self::PointX { .. }
-            | Self::PointY { .. } => ExprType::Number,
+            | Self::PointY { .. }
+            | Self::Sin { .. }
+            | Self::Cos { .. }
+            | Self::Tan { .. }
+            | Self::Abs { .. } => ExprType::Number,
             Self::PointPoint { .. }
             | Self::AngleBisector { .. }
             | Self::ParallelThrough { .. }
@@ -730,6 +788,7 @@ impl From<ExprKind> for geo_aid_figure::ExpressionKind {
             ExprKind::LineLineIntersection { k, l } => Self::LineLineIntersection { k, l },
             ExprKind::AveragePoint { items } => Self::AveragePoint { items },
             ExprKind::CircleCenter { circle } => Self::CircleCenter { circle },
+            ExprKind::Rotate { point, angle } => Self::Rotate { point, angle },
             ExprKind::Sum { plus, minus } => Self::Sum { plus, minus },
             ExprKind::Product { times, by } => Self::Product { times, by },
             ExprKind::Const { value } => Self::Const {
@@ -748,6 +807,10 @@ impl From<ExprKind> for geo_aid_figure::ExpressionKind {
             ExprKind::TwoLineAngle { k, l } => Self::TwoLineAngle { k, l },
             ExprKind::PointX { point } => Self::PointX { point },
             ExprKind::PointY { point } => Self::PointY { point },
+            ExprKind::Sin { angle } => Self::Sin { angle },
+            ExprKind::Cos { angle } => Self::Cos { angle },
+            ExprKind::Tan { angle } => Self::Tan { angle },
+            ExprKind::Abs { value } => Self::Abs { value },
             ExprKind::PointPoint { p, q } => Self::PointPoint { p, q },
             ExprKind::AngleBisector { p, q, r } => Self::AngleBisector { p, q, r },
             ExprKind::ParallelThrough { point, line } => Self::ParallelThrough { point, line },
@@ -780,6 +843,10 @@ impl FindEntities for ExprKind {
             Self::CircleCenter { circle: x }
             | Self::PointX { point: x }
             | Self::PointY { point: x }
+            | Self::Sin { angle: x }
+            | Self::Cos { angle: x }
+            | Self::Tan { angle: x }
+            | Self::Abs { value: x }
             | Self::PartialPower { value: x, .. } => {
                 set.extend(previous[x.0].iter().copied());
             }
@@ -793,6 +860,7 @@ impl FindEntities for ExprKind {
             }
             Self::PointPointDistance { p: a, q: b }
             | Self::PointLineDistance { point: a, line: b }
+            | Self::Rotate { point: a, angle: b }
             | Self::TwoLineAngle { k: a, l: b }
             | Self::LineLineIntersection { k: a, l: b }
             | Self::ParallelThrough { point: a, line: b }
@@ -839,9 +907,58 @@ impl FromUnrolled<UnrolledPoint> for ExprKind {
                 UnrolledCircle::Circle(center, _) => return math.load_no_store(center),
                 UnrolledCircle::Generic(_) => unreachable!(),
             },
+            UnrolledPoint::Add(p, q) => ExprKind::Sum {
+                plus: vec![math.load(p), math.load(q)],
+                minus: Vec::new(),
+            },
+            UnrolledPoint::Subtract(p, q) => ExprKind::Sum {
+                plus: vec![math.load(p)],
+                minus: vec![math.load(q)],
+            },
+            UnrolledPoint::Multiply(p, v) => ExprKind::Product {
+                times: vec![math.load(p), math.load(v)],
+                by: Vec::new(),
+            },
+            UnrolledPoint::Rotate(p, angle) => ExprKind::Rotate {
+                point: math.load(p),
+                angle: math.load(angle),
+            },
             UnrolledPoint::Free => ExprKind::Entity {
                 id: math.add_point(),
             },
+            UnrolledPoint::OnLine(line) => {
+                let line = math.load(line);
+                ExprKind::Entity {
+                    id: math.add_point_on_line(line),
+                }
+            }
+            UnrolledPoint::OnCircle(circle) => {
+                let circle = math.load(circle);
+                ExprKind::Entity {
+                    id: math.add_point_on_circle(circle),
+                }
+            }
+            UnrolledPoint::Fixed(x, y) => {
+                // The point is bound to x + yi.
+                let i = math.store(
+                    ExprKind::Const {
+                        value: ProcNum::i(),
+                    },
+                    ExprType::Number,
+                );
+                let y = ExprKind::Product {
+                    times: vec![math.load(y), i],
+                    by: Vec::new(),
+                };
+                let coords = ExprKind::Sum {
+                    plus: vec![math.load(x), math.store(y, ExprType::Number)],
+                    minus: Vec::new(),
+                };
+                let coords = math.store(coords, ExprType::Point);
+                ExprKind::Entity {
+                    id: math.add_bound_point(coords),
+                }
+            }
             UnrolledPoint::Generic(_) => unreachable!(),
         };
 
@@ -933,6 +1050,18 @@ impl FromUnrolled<unroll::Scalar> for ExprKind {
             UnrolledScalar::PointY(point) => ExprKind::PointY {
                 point: math.load(point),
             },
+            UnrolledScalar::Sin(angle) => ExprKind::Sin {
+                angle: math.load(angle),
+            },
+            UnrolledScalar::Cos(angle) => ExprKind::Cos {
+                angle: math.load(angle),
+            },
+            UnrolledScalar::Tan(angle) => ExprKind::Tan {
+                angle: math.load(angle),
+            },
+            UnrolledScalar::Abs(value) => ExprKind::Abs {
+                value: math.load(value),
+            },
             UnrolledScalar::Generic(_) => unreachable!(),
         };
 
@@ -1022,10 +1151,14 @@ impl Normalize for ExprKind {
 
         match self {
             Self::CircleCenter { .. }
+            | Self::Rotate { .. }
             | Self::PointLineDistance { .. }
             | Self::PointX { .. }
             | Self::PointY { .. }
-            | Self::PartialPower { .. }
+            | Self::Sin { .. }
+            | Self::Cos { .. }
+            | Self::Tan { .. }
+            | Self::Abs { .. }
             | Self::ConstructCircle { .. }
             | Self::Const { .. }
             | Self::ThreePointAngleDir { .. } // DO NOT NORMALIZE DIRECTED ANGLES
@@ -1043,9 +1176,49 @@ impl Normalize for ExprKind {
             }
             Self::Sum { plus, minus } => {
                 normalize_sum(plus, minus, math);
+
+                // Fold sums of constants.
+                if minus.is_empty() {
+                    new_self = match plus.as_slice() {
+                        [] => Some(Self::Const {
+                            value: ProcNum::zero(),
+                        }),
+                        [item] => match &math.at(item).kind {
+                            Self::Const { value } => Some(Self::Const {
+                                value: value.clone(),
+                            }),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                }
             }
             Self::Product { times, by } => {
                 normalize_product(times, by, math);
+
+                // Fold products of constants.
+                if by.is_empty() {
+                    new_self = match times.as_slice() {
+                        [] => Some(Self::Const {
+                            value: ProcNum::one(),
+                        }),
+                        [item] => match &math.at(item).kind {
+                            Self::Const { value } => Some(Self::Const {
+                                value: value.clone(),
+                            }),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                }
+            }
+            Self::PartialPower { value, exponent } => {
+                // Fold powers of constants, if exact.
+                if let Self::Const { value } = &math.at(value).kind {
+                    new_self = value
+                        .partial_pow(exponent)
+                        .map(|value| Self::Const { value });
+                }
             }
             Self::ParallelThrough { point, line } => {
                 // This is technically a move, although ugly, so we clone.
@@ -1419,6 +1592,8 @@ pub struct Rule {
     pub weight: ProcNum,
     /// Entities this rule affects.
     pub entities: Vec<EntityId>,
+    /// The rule as written in the script. `None` for rules added by the compiler.
+    pub source: Option<String>,
 }
 
 impl Display for Rule {
@@ -1500,6 +1675,7 @@ impl Rule {
             kind: RuleKind::load(rule, math),
             weight: rule.weight.clone(),
             entities: Vec::new(),
+            source: Some(rule.to_string()),
         }
     }
 }
@@ -1596,7 +1772,7 @@ pub enum EntityKind {
     /// A special distance unit entity, effectively a free real.
     DistanceUnit,
     /// A bind. Never shows up past the compilation stage. It serves as a temporary
-    /// value in-between compilation steps.
+    /// value in-between compilation steps and as a way to fix a point at given coordinates.
     Bind(VarIndex),
 }
 
@@ -1787,6 +1963,21 @@ impl Math {
         self.add_entity(EntityKind::FreePoint)
     }
 
+    /// Add a free point entity clipped to a line.
+    pub fn add_point_on_line(&mut self, line: VarIndex) -> EntityId {
+        self.add_entity(EntityKind::PointOnLine { line })
+    }
+
+    /// Add a free point entity clipped to a circle.
+    pub fn add_point_on_circle(&mut self, circle: VarIndex) -> EntityId {
+        self.add_entity(EntityKind::PointOnCircle { circle })
+    }
+
+    /// Add a point entity bound to the given expression.
+    pub fn add_bound_point(&mut self, expr: VarIndex) -> EntityId {
+        self.add_entity(EntityKind::Bind(expr))
+    }
+
     /// Add a free real entity.
     pub fn add_real(&mut self) -> EntityId {
         self.add_entity(EntityKind::FreeReal)
@@ -1824,11 +2015,22 @@ impl Build {
 ///
 /// # Returns
 /// `true` if an optimization was performed. `false` otherwise.
-fn optimize_rules(rules: &mut Vec<Option<Rule>>, math: &mut Math) -> bool {
+fn optimize_rules(rules: &mut Vec<Option<Rule>>, math: &mut Math, flags: &Flags) -> bool {
     let mut performed = false;
 
     for rule in rules.iter_mut() {
-        let rule_performed = ZeroLineDst::process(rule, math)
+        // Optimized rules become exact constraints, which is never weaker than an increased weight.
+        // Down-weighted (e.g. soft) rules must be able to give way, so they're kept as they are.
+        if rule
+            .as_ref()
+            .is_some_and(|rule| rule.weight < ProcNum::one())
+        {
+            continue;
+        }
+
+        let rule_performed = (flags.distance_literals == DistanceLiterals::Solve
+            && SolveDistanceUnit::process(rule, math))
+            | ZeroLineDst::process(rule, math)
             | RightAngle::process(rule, math)
             | EqPointDst::process(rule, math)
             | EqExpressions::process(rule, math);
@@ -1975,14 +2177,30 @@ fn read_flags(flags: &HashMap<String, Flag>) -> Flags {
     Flags {
         optimizations: Optimizations {},
         point_inequalities: flags["point_inequalities"].as_bool().unwrap(),
+        scale_point_inequalities: flags["scale_point_inequalities"].as_bool().unwrap(),
+        distance_literals: match flags["distance_literals"].as_ident().unwrap().as_str() {
+            "solve" => DistanceLiterals::Solve,
+            _ => DistanceLiterals::Adjust,
+        },
     }
 }
 
 /// Optimize, Normalize, Repeat
-fn optimize_cycle(rules: &mut Vec<Option<Rule>>, math: &mut Math, items: &mut Vec<Item>) {
+fn optimize_cycle(
+    rules: &mut Vec<Option<Rule>>,
+    math: &mut Math,
+    items: &mut Vec<Item>,
+    flags: &Flags,
+) {
     let mut entity_map = Vec::new();
     loop {
-        if !optimize_rules(rules, math) {
+        // Binds must be resolved even if no optimization was applied.
+        if !optimize_rules(rules, math, flags)
+            && !math
+                .entities
+                .iter()
+                .any(|ent| matches!(ent, EntityKind::Bind(_)))
+        {
             break;
         }
 
@@ -2097,7 +2315,9 @@ pub fn load_script(input: &str) -> Result<Intermediate, Vec<Error>> {
     // Get the math out of the `Expand`.
     let mut math = expand.math;
 
-    optimize_cycle(&mut rules, &mut math, &mut build.items);
+    let flags = read_flags(&unrolled.flags);
+
+    optimize_cycle(&mut rules, &mut math, &mut build.items, &flags);
 
     // Now everything that could be normalized is normalized.
     // Unfortunately, normalization can introduce forward referencing, which is not what we want.
@@ -2126,11 +2346,9 @@ pub fn load_script(input: &str) -> Result<Intermediate, Vec<Error>> {
     // We can also finalize rules:
     let mut rules: Vec<_> = rules.into_iter().flatten().collect();
 
-    let flags = read_flags(&unrolled.flags);
-
     // And add point inequalities
     if flags.point_inequalities {
-        for i in new_entities
+        let points: Vec<_> = new_entities
             .iter()
             .enumerate()
             .filter(|ent| {
@@ -2142,26 +2360,24 @@ pub fn load_script(input: &str) -> Result<Intermediate, Vec<Error>> {
                 )
             })
             .map(|x| x.0)
-        {
-            for j in new_entities
-                .iter()
-                .enumerate()
-                .skip(i + 1)
-                .filter(|ent| {
-                    matches!(
-                        ent.1,
-                        EntityKind::PointOnLine { .. }
-                            | EntityKind::FreePoint
-                            | EntityKind::PointOnCircle { .. }
-                    )
-                })
-                .map(|x| x.0)
-            {
+            .collect();
+
+        // Every point takes part in `n - 1` inequalities. If requested, their weights are scaled
+        // so that each point's inequalities weigh 1 in total, otherwise they'd dominate big figures.
+        let weight = if flags.scale_point_inequalities {
+            ProcNum::one() / &ProcNum::from_usize(points.len().max(2) - 1).unwrap()
+        } else {
+            ProcNum::one()
+        };
+
+        for (k, &i) in points.iter().enumerate() {
+            for &j in &points[k + 1..] {
                 let ent1 = math.store(ExprKind::Entity { id: EntityId(i) }, ExprType::Point);
                 let ent2 = math.store(ExprKind::Entity { id: EntityId(j) }, ExprType::Point);
                 rules.push(Rule {
-                    weight: ProcNum::one(),
+                    weight: weight.clone(),
                     entities: Vec::new(),
+                    source: None,
                     kind: RuleKind::Invert(Box::new(RuleKind::PointEq(ent1, ent2))),
                 });
             }
@@ -1,9 +1,11 @@
 //! Module containing all of optimizing functionality that requries finding patterns
 //! and enables rule removal and entity redefining.
 
-use crate::math::{ContainsEntity, DeepClone, EntityKind, ExprKind, ExprType, Math, VarIndex};
+use crate::math::{
+    ContainsEntity, DeepClone, EntityId, EntityKind, ExprKind, ExprType, Math, VarIndex,
+};
 use crate::token::number::ProcNum;
-use num_traits::{FromPrimitive, Zero};
+use num_traits::{FromPrimitive, One, Zero};
 
 use super::{Rule, RuleKind};
 
@@ -228,3 +230,79 @@ impl RightAngle {
         true
     }
 }
+
+/// If a distance unit multiple is equal to something independent of it, the unit can be solved for.
+/// Only used with `distance_literals = solve`.
+pub struct SolveDistanceUnit;
+
+impl SolveDistanceUnit {
+    /// If the expression is a constant multiple of the distance unit, get the unit and the multiplier.
+    fn as_unit_multiple(expr: &VarIndex, math: &Math) -> Option<(EntityId, ProcNum)> {
+        match &math.at(expr).kind {
+            ExprKind::Entity { id } => matches!(math.entities[id.0], EntityKind::DistanceUnit)
+                .then(|| (*id, ProcNum::one())),
+            ExprKind::PartialPower { value, exponent } if exponent.is_one() => {
+                Self::as_unit_multiple(value, math)
+            }
+            ExprKind::Product { times, by } if by.is_empty() => {
+                let mut unit = None;
+                let mut multiplier = ProcNum::one();
+
+                for item in times {
+                    if let ExprKind::Const { value } = &math.at(item).kind {
+                        multiplier *= value;
+                    } else if unit.is_none() {
+                        unit = Some(Self::as_unit_multiple(item, math)?);
+                    } else {
+                        return None;
+                    }
+                }
+
+                let (id, inner) = unit?;
+                Some((id, multiplier * inner))
+            }
+            _ => None,
+        }
+    }
+
+    // Assumes that if the rule has a unit multiple, it's on the right of a = b
+    fn process_x_unit(a: &VarIndex, b: &VarIndex, math: &mut Math) -> bool {
+        let Some((unit, multiplier)) = Self::as_unit_multiple(b, math) else {
+            return false;
+        };
+
+        if multiplier.is_zero() || a.contains_entity(unit, math) {
+            return false;
+        }
+
+        // a = multiplier * unit, so unit = a / multiplier
+        let multiplier = math.store(ExprKind::Const { value: multiplier }, ExprType::Number);
+        let solved = math.store(
+            ExprKind::Product {
+                times: vec![a.clone()],
+                by: vec![multiplier],
+            },
+            ExprType::Number,
+        );
+        math.entities[unit.0] = EntityKind::Bind(solved);
+
+        true
+    }
+
+    pub fn process(rule: &mut Option<Rule>, math: &mut Math) -> bool {
+        let Some(Rule {
+            kind: RuleKind::NumberEq(a, b),
+            ..
+        }) = rule
+        else {
+            return false;
+        };
+
+        if Self::process_x_unit(a, b, math) || Self::process_x_unit(b, a, math) {
+            *rule = None;
+            true
+        } else {
+            false
+        }
+    }
+}
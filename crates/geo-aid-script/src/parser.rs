@@ -20,7 +20,7 @@ use super::{
         number::CompExponent, Ampersant, Asterisk, At, Caret, Colon, Comma, Dollar, Dot, Eq,
         Exclamation, Gt, Gteq, Ident, LBrace, LParen, LSquare, Let, Lt, Lteq, Minus, NamedIdent,
         Number, Plus, Question, RBrace, RParen, RSquare, Semi, Slash, Span, StrLit, TokInteger,
-        Token,
+        Token, UnitSuffix, Vertical,
     },
     unit, ComplexUnit, Error,
 };
@@ -207,6 +207,36 @@ impl<'t, I: Iterator<Item = &'t Token> + Clone> InputStream<'t, I> {
     pub fn eof(&mut self) -> bool {
         self.it.peek().is_none()
     }
+
+    /// Skip the rest of a statement. Stops after a semicolon or a flag set's closing brace
+    /// outside of any brackets, or before a `let` or a flag starting the next statement.
+    pub fn skip_statement(&mut self) {
+        let mut depth = 0_usize;
+        let mut first = true;
+
+        while let Some(&token) = self.it.peek() {
+            if depth == 0 && !first && matches!(token, Token::Let(_) | Token::At(_)) {
+                break;
+            }
+
+            first = false;
+            self.it.next();
+
+            match token {
+                Token::LParen(_) | Token::LSquare(_) | Token::LBrace(_) => depth += 1,
+                Token::RParen(_) | Token::RSquare(_) => depth = depth.saturating_sub(1),
+                Token::RBrace(_) => {
+                    depth = depth.saturating_sub(1);
+
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                Token::Semi(_) if depth == 0 => break,
+                _ => (),
+            }
+        }
+    }
 }
 
 /// A binary operator, like `+`, `-`, `*` or `/`.
@@ -550,13 +580,22 @@ pub enum SimpleExpressionKind {
     /// A named (variable, field or function call)
     Name(Name),
     /// A raw number
-    Number(Number),
+    Number(NumberLit),
     /// An explicit iterator.
     ExplicitIterator(ExplicitIterator),
     /// A point collection construction
     PointCollection(PointCollectionConstructor),
 }
 
+/// A number literal with a possible unit suffix.
+#[derive(Debug, Parse)]
+pub struct NumberLit {
+    /// The number itself.
+    pub number: Number,
+    /// The unit suffix, if any.
+    pub unit: Option<UnitSuffix>,
+}
+
 /// A parsed function call
 #[derive(Debug, Parse)]
 pub struct ExprCall {
@@ -727,17 +766,25 @@ pub struct LetStatement {
 }
 
 /// `lhs ruleop rhs`.
-/// Defines a rule.
+/// A single rule.
 #[derive(Debug, Parse)]
-pub struct RuleStatement {
-    /// Display properties.
-    pub display: Option<DisplayProperties>,
+pub struct SingleRule {
     /// Left hand side
     pub lhs: Expression<true>,
     /// Rule operator
     pub op: RuleOperator,
     /// Right hand side
     pub rhs: Expression<true>,
+}
+
+/// `lhs ruleop rhs | lhs ruleop rhs | ...`.
+/// Defines a rule. If more than one rule is given, at least one of them must be satisfied.
+#[derive(Debug, Parse)]
+pub struct RuleStatement {
+    /// Display properties.
+    pub display: Option<DisplayProperties>,
+    /// The alternative rules.
+    pub rules: Punctuated<SingleRule, Vertical>,
     /// The ending semicolon.
     pub semi: Semi,
 }
@@ -1075,6 +1122,29 @@ impl FromProperty for bool {
     }
 }
 
+impl FromProperty for u8 {
+    fn from_property(property: PropertyValue) -> Result<Self, Error> {
+        match property {
+            PropertyValue::Number(Number::Integer(i)) => i
+                .parsed
+                .parse()
+                .map_err(|_| Error::NumberExpected { error_span: i.span }),
+            PropertyValue::Number(num) => Err(Error::NumberExpected {
+                error_span: num.get_span(),
+            }),
+            PropertyValue::RawString(s) => Err(Error::NumberExpected {
+                error_span: s.get_span(),
+            }),
+            PropertyValue::String(s) => Err(Error::NumberExpected {
+                error_span: s.get_span(),
+            }),
+            PropertyValue::Ident(ident) => Err(Error::NumberExpected {
+                error_span: ident.get_span(),
+            }),
+        }
+    }
+}
+
 impl FromProperty for String {
     fn from_property(property: PropertyValue) -> Result<String, Error> {
         match property {
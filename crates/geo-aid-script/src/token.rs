@@ -341,6 +341,7 @@ pub enum Token {
     Exclamation(Exclamation),
     Ident(Ident),
     Number(Number),
+    UnitSuffix(UnitSuffix),
     Dollar(Dollar),
     Ampersant(Ampersant),
     LBrace(LBrace),
@@ -397,6 +398,7 @@ impl Display for Token {
                 Number::Integer(v) => write!(f, "{}", v.parsed),
                 Number::Float(v) => write!(f, "{}", v.parsed),
             },
+            Self::UnitSuffix(suffix) => write!(f, "{suffix}"),
         }
     }
 }
@@ -425,6 +427,7 @@ impl Token {
             Self::Exclamation(v) => v.span,
             Self::Ident(v) => v.get_span(),
             Self::Number(v) => v.get_span(),
+            Self::UnitSuffix(v) => v.get_span(),
             Self::Dollar(v) => v.span,
             Self::At(v) => v.span,
             Self::LBrace(v) => v.span,
@@ -608,6 +611,37 @@ impl Display for Number {
     }
 }
 
+/// A unit suffix directly following a number literal, like the `deg` in `30deg`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Parse)]
+#[parse(token)]
+pub enum UnitSuffix {
+    /// A `deg` or `°` suffix.
+    Degrees(Degrees),
+    /// A `rad` suffix.
+    Radians(Radians),
+}
+
+impl Display for UnitSuffix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Degrees(_) => write!(f, "deg"),
+            Self::Radians(_) => write!(f, "rad"),
+        }
+    }
+}
+
+/// A degrees unit suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Degrees {
+    pub span: Span,
+}
+
+/// A radians unit suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Radians {
+    pub span: Span,
+}
+
 /// An integer.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TokInteger {
@@ -817,6 +851,40 @@ fn read_number<I: Iterator<Item = char>>(it: &mut Peekable<I>, position: &mut Po
     }
 }
 
+/// Read an optional unit suffix directly following a number literal. If an identifier follows
+/// instead, it is read as usual.
+fn read_unit_suffix<I: Iterator<Item = char>>(
+    it: &mut Peekable<I>,
+    position: &mut Position,
+) -> Option<Token> {
+    match it.peek() {
+        Some('°') => {
+            let sp = span!(
+                position.line,
+                position.column,
+                position.line,
+                position.column + 1
+            );
+            position.column += 1;
+            it.next();
+
+            Some(Token::UnitSuffix(UnitSuffix::Degrees(Degrees { span: sp })))
+        }
+        Some(&c) if c.is_alphabetic() || c == '_' => {
+            let (sp, ident) = read_identifier(it, position);
+
+            Some(match ident.as_str() {
+                "deg" => Token::UnitSuffix(UnitSuffix::Degrees(Degrees { span: sp })),
+                "rad" => Token::UnitSuffix(UnitSuffix::Radians(Radians { span: sp })),
+                "let" => Token::Let(Let { span: sp }),
+                "or" => Token::Vertical(Vertical { span: sp }),
+                _ => Token::Ident(dispatch_ident(sp, ident)),
+            })
+        }
+        _ => None,
+    }
+}
+
 /// Decides whether the given string is a standard named identifier or a point collection.
 fn dispatch_ident(sp: Span, ident: String) -> Ident {
     let mut collection = PointCollection {
@@ -991,6 +1059,63 @@ fn tokenize_special<I: Iterator<Item = char>>(
     Ok(())
 }
 
+/// Skips a line (`//`) or a block (`/* */`) comment. Returns `false` if the iterator
+/// is not at the start of a comment.
+fn skip_comment<I: Iterator<Item = char> + Clone>(
+    it: &mut Peekable<I>,
+    position: &mut Position,
+) -> Result<bool, Error> {
+    let mut lookahead = it.clone();
+    lookahead.next();
+
+    match lookahead.next() {
+        Some('/') => {
+            position.line += 1;
+            position.column = 1;
+            for c in it.by_ref() {
+                if c == '\n' {
+                    break;
+                }
+            }
+
+            Ok(true)
+        }
+        Some('*') => {
+            let begin_pos = *position;
+
+            it.next();
+            it.next();
+            position.column += 2;
+
+            let mut previous = None;
+            for c in it.by_ref() {
+                if c == '\n' {
+                    position.line += 1;
+                    position.column = 1;
+                } else {
+                    position.column += 1;
+                }
+
+                if previous == Some('*') && c == '/' {
+                    return Ok(true);
+                }
+
+                previous = Some(c);
+            }
+
+            Err(Error::UnclosedComment {
+                error_span: span!(
+                    begin_pos.line,
+                    begin_pos.column,
+                    begin_pos.line,
+                    begin_pos.column + 2
+                ),
+            })
+        }
+        _ => Ok(false),
+    }
+}
+
 /// Tokenizes the given script (turns it into a series of tokens).
 ///
 /// # Errors
@@ -1017,10 +1142,13 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
 
                     tokens.push(match ident.as_str() {
                         "let" => Token::Let(Let { span: sp }),
+                        // `or` is an alias for `|`.
+                        "or" => Token::Vertical(Vertical { span: sp }),
                         _ => Token::Ident(dispatch_ident(sp, ident)),
                     });
                 } else if c.is_ascii_digit() {
                     tokens.push(Token::Number(read_number(&mut it, &mut position)));
+                    tokens.extend(read_unit_suffix(&mut it, &mut position));
                 } else if c == '#' {
                     position.line += 1;
                     position.column = 1;
@@ -1029,6 +1157,8 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
                             break;
                         }
                     }
+                } else if c == '/' && skip_comment(&mut it, &mut position)? {
+                    // The comment is skipped.
                 } else if c == '"' {
                     let s = read_string(&mut it, &mut position)?;
 
@@ -241,6 +241,43 @@ impl ProcNum {
         geometry::Complex::new(self.0.re.to_f64().unwrap(), self.0.im.to_f64().unwrap())
     }
 
+    /// Turn this into a rational exponent. Returns `None` if the number is not real
+    /// or does not fit in the exponent type.
+    #[must_use]
+    pub fn to_exponent(&self) -> Option<CompExponent> {
+        if self.0.im.is_zero() {
+            Some(CompExponent::new(
+                self.0.re.numer().to_i64()?,
+                self.0.re.denom().to_i64()?,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Raise both parts of this number (real and imaginary) to the given power.
+    /// Returns `None` if the result cannot be represented exactly, that is, if the
+    /// exponent is not an integer or a zero part would be divided by.
+    #[must_use]
+    pub fn partial_pow(&self, exponent: &CompExponent) -> Option<Self> {
+        if !exponent.is_integer() {
+            return None;
+        }
+
+        let exp = i32::try_from(*exponent.numer()).ok()?;
+        if exp < 0 && (self.0.re.is_zero() || self.0.im.is_zero()) {
+            return None;
+        }
+
+        Some(Self(Complex::new(self.0.re.pow(exp), self.0.im.pow(exp))))
+    }
+
+    /// The imaginary unit.
+    #[must_use]
+    pub fn i() -> Self {
+        Self(Complex::i())
+    }
+
     /// Pi as this number type.
     ///
     /// # Panics
@@ -411,7 +448,7 @@ impl From<&Number> for ProcNum {
                     decimal += Complex::from_u8(*digit).unwrap();
                 }
 
-                Self(integral + decimal)
+                Self(integral + decimal.unscale(BigRational::from_integer(denominator)))
             }
         }
     }
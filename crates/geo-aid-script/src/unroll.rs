@@ -9,7 +9,7 @@ use crate::span;
 use flags::FlagSetConstructor;
 use geo_aid_derive::CloneWithNode;
 use geo_aid_figure::Style;
-use num_traits::{One, Zero};
+use num_traits::{FromPrimitive, One, Zero};
 use std::fmt::Formatter;
 use std::mem;
 use std::{
@@ -24,17 +24,18 @@ use std::{
 use crate::figure::SpannedMathString;
 use crate::ty;
 use library::macros::index;
+use library::vector;
 
 use self::context::CompileContext;
 use self::figure::{
     AnyExprNode, BundleNode, CircleNode, CollectionNode, EmptyNode, FromExpr, HierarchyNode,
-    LineNode, LineType, MaybeUnset, Node, PCNode, PointNode, ScalarNode,
+    LineNode, LineType, MaybeUnset, Node, PCNode, PointNode, ScalarNode, Strength,
 };
 use self::library::Library;
 
 use super::parser::{
-    ExprBinop, ExprCall, FieldIndex, FromProperty, InputStream, Name, PointCollectionConstructor,
-    RefStatement,
+    ExprBinop, ExprCall, FieldIndex, FromProperty, InputStream, Name, NumberLit,
+    PointCollectionConstructor, RefStatement,
 };
 use super::token::number::{CompExponent, ProcNum};
 use super::token::Number;
@@ -42,9 +43,9 @@ use super::{
     parser::{
         BinaryOperator, DisplayProperties, ExplicitIterator, Expression, ImplicitIterator,
         LetStatement, Parse, PredefinedRuleOperator, PropertyValue, Punctuated, RuleOperator,
-        RuleStatement, SimpleExpression, SimpleExpressionKind, Statement, Type,
+        RuleStatement, SimpleExpression, SimpleExpressionKind, SingleRule, Statement, Type,
     },
-    token::{self, Ident, NamedIdent, PointCollection as PCToken, Span},
+    token::{self, Ident, NamedIdent, PointCollection as PCToken, Span, UnitSuffix, Vertical},
     unit, ComplexUnit, Error,
 };
 
@@ -349,7 +350,7 @@ impl<'r> IterTreeIterator<'r> {
 
     /// Add a iterator tree node.
     fn add_node(&mut self, node: &'r IterNode) {
-        if node.len() > 0 {
+        if !node.is_empty() {
             let mut visited = Vec::new();
             let mut lengths = Vec::new();
 
@@ -437,7 +438,7 @@ impl<'r> IterTreeIterator<'r> {
 }
 
 /// The kind on the unrolled rule.
-#[derive(Debug)]
+#[derive(Debug, CloneWithNode)]
 pub enum UnrolledRuleKind {
     /// A point equality rule (distance of 0)
     PointEq(Expr<Point>, Expr<Point>),
@@ -446,7 +447,7 @@ pub enum UnrolledRuleKind {
     /// a > b
     Gt(Expr<Scalar>, Expr<Scalar>),
     /// One of the rules must be true.
-    Alternative(Vec<UnrolledRule>),
+    Alternative(ClonedVec<UnrolledRule>),
     /// Bias entities in an expression. Can alter behavior of some engines.
     Bias(AnyExpr),
 }
@@ -689,8 +690,22 @@ pub enum Point {
     LineLineIntersection(Expr<Line>, Expr<Line>),
     /// Center of a circle.
     CircleCenter(Expr<Circle>),
+    /// Sum of points as complex numbers.
+    Add(Expr<Point>, Expr<Point>),
+    /// Difference of points as complex numbers.
+    Subtract(Expr<Point>, Expr<Point>),
+    /// A point as a complex number multiplied by a scalar.
+    Multiply(Expr<Point>, Expr<Scalar>),
+    /// A point as a complex number rotated about the origin by an angle.
+    Rotate(Expr<Point>, Expr<Scalar>),
     /// A free point.
     Free,
+    /// A free point on a line.
+    OnLine(Expr<Line>),
+    /// A free point on a circle.
+    OnCircle(Expr<Circle>),
+    /// A point fixed at given coordinates.
+    Fixed(Expr<Scalar>, Expr<Scalar>),
 }
 
 impl Point {
@@ -806,7 +821,14 @@ impl Display for Point {
             Self::CircleCenter(circle) => {
                 write!(f, "{circle}.center")
             }
+            Self::Add(p, q) => write!(f, "{p} + {q}"),
+            Self::Subtract(p, q) => write!(f, "{p} - {q}"),
+            Self::Multiply(p, v) => write!(f, "{p} * {v}"),
+            Self::Rotate(p, angle) => write!(f, "rotate({p}, {angle})"),
             Self::Free => write!(f, "Free point"),
+            Self::OnLine(line) => write!(f, "Free point on {line}"),
+            Self::OnCircle(circle) => write!(f, "Free point on {circle}"),
+            Self::Fixed(x, y) => write!(f, "fixed({x}, {y})"),
         }
     }
 }
@@ -1162,6 +1184,14 @@ pub enum ScalarData {
     PointX(Expr<Point>),
     /// Y coordinate of a point
     PointY(Expr<Point>),
+    /// Sine of an angle
+    Sin(Expr<Scalar>),
+    /// Cosine of an angle
+    Cos(Expr<Scalar>),
+    /// Tangent of an angle
+    Tan(Expr<Scalar>),
+    /// Absolute value
+    Abs(Expr<Scalar>),
     /// A free scalar.
     Free,
 }
@@ -1204,6 +1234,10 @@ impl Display for ScalarData {
             Self::Pow(base, exponent) => write!(f, "({base})^{exponent}"),
             Self::PointX(expr) => write!(f, "{expr}.x"),
             Self::PointY(expr) => write!(f, "{expr}.y"),
+            Self::Sin(expr) => write!(f, "sin({expr})"),
+            Self::Cos(expr) => write!(f, "cos({expr})"),
+            Self::Tan(expr) => write!(f, "tan({expr})"),
+            Self::Abs(expr) => write!(f, "abs({expr})"),
             Self::Free => write!(f, "Free scalar"),
         }
     }
@@ -1370,10 +1404,16 @@ impl Expr<Scalar> {
                         | ScalarData::CircleRadius(_)
                         | ScalarData::PointX(_)
                         | ScalarData::PointY(_)
+                        | ScalarData::Sin(_)
+                        | ScalarData::Cos(_)
+                        | ScalarData::Tan(_)
                         | ScalarData::SetUnit(_, _) => unreachable!(), // Always concrete
                         ScalarData::Negate(v) => {
                             ScalarData::Negate(v.clone_without_node().convert_unit(unit, context))
                         }
+                        ScalarData::Abs(v) => {
+                            ScalarData::Abs(v.clone_without_node().convert_unit(unit, context))
+                        }
                         ScalarData::Add(a, b) => {
                             // Both operands are guaranteed to be unit-less here.
                             ScalarData::Add(
@@ -2441,7 +2481,7 @@ impl<T: CloneWithNode + Displayed + Dummy> Dummy for Expr<T> {
 }
 
 /// An unrolled rule with a kind and additional data.
-#[derive(Debug)]
+#[derive(Debug, CloneWithNode)]
 pub struct UnrolledRule {
     /// The kind of this rule
     pub kind: UnrolledRuleKind,
@@ -2608,7 +2648,7 @@ impl Unroll for Ident {
                             col.collection
                                 .iter()
                                 .map(|item| {
-                                    fetch_variable(context, &format!("{item}"), col.span)
+                                    fetch_variable(context, &format!("{item}"), item.span)
                                         .convert::<Point>(context)
                                 })
                                 .collect::<Vec<_>>()
@@ -2664,11 +2704,17 @@ impl Unroll for ExprCall {
                     return ret.boxed(self.get_span());
                 }
 
-                context.push_error(Error::OverloadNotFound {
-                    error_span: self.get_span(),
-                    function_name: func_name.clone(),
-                    params: params.iter().map(AnyExpr::get_type).collect(),
-                });
+                let error = func
+                    .overloads
+                    .iter()
+                    .find_map(|x| x.get_mismatch_error(&params, self.get_span()))
+                    .unwrap_or_else(|| Error::OverloadNotFound {
+                        error_span: self.get_span(),
+                        function_name: func_name.clone(),
+                        params: params.iter().map(AnyExpr::get_type).collect(),
+                        candidates: func.overloads.iter().map(|x| x.get_signature()).collect(),
+                    });
+                context.push_error(error);
 
                 Expr {
                     data: Rc::new(Unknown::dummy()),
@@ -2918,6 +2964,36 @@ impl Unroll for Number {
     }
 }
 
+impl Unroll for NumberLit {
+    fn unroll(
+        &self,
+        context: &mut CompileContext,
+        library: &Library,
+        it_index: &HashMap<u8, usize>,
+        display: Properties,
+    ) -> AnyExpr {
+        // Angles are always stored in radians.
+        let value = match &self.unit {
+            None => return self.number.unroll(context, library, it_index, display),
+            Some(UnitSuffix::Degrees(_)) => {
+                ProcNum::from(&self.number) * ProcNum::pi() / &ProcNum::from_i32(180).unwrap()
+            }
+            Some(UnitSuffix::Radians(_)) => ProcNum::from(&self.number),
+        };
+
+        display.finish(context);
+
+        AnyExpr::Scalar(Expr {
+            data: Rc::new(Scalar {
+                unit: Some(unit::ANGLE),
+                data: ScalarData::Number(value),
+            }),
+            span: self.get_span(),
+            node: None,
+        })
+    }
+}
+
 impl Unroll for ExplicitIterator {
     fn unroll(
         &self,
@@ -3021,6 +3097,15 @@ impl<const ITER: bool> Unroll for ExprBinop<ITER> {
             .rhs
             .unroll(context, library, it_index, Properties::default());
 
+        if matches!(
+            self.operator,
+            BinaryOperator::Add(_) | BinaryOperator::Sub(_)
+        ) && vector::is_vector(&rhs)
+            && (vector::is_vector(&lhs) || lhs.can_convert_to(ty::POINT))
+        {
+            return vector::unroll_binop(&self.operator, lhs, rhs, context, display);
+        }
+
         let mut lhs = if lhs.can_convert_to(ty::SCALAR_UNKNOWN) {
             lhs.convert::<Scalar>(context)
         } else {
@@ -3091,15 +3176,19 @@ impl<const ITER: bool> Unroll for ExprBinop<ITER> {
                 let lhs = lhs.specify_unit(context);
 
                 let rhs = rhs.specify_unit(context);
+                let (lhs_unit, rhs_unit) = (lhs.data.unit.unwrap(), rhs.data.unit.unwrap());
                 let mut expr = Expr {
                     span: self.get_span(),
-                    data: Rc::new(Scalar {
-                        unit: Some(lhs.data.unit.unwrap() * rhs.data.unit.as_ref().unwrap()),
-                        data: match &self.operator {
-                            BinaryOperator::Mul(_) => ScalarData::Multiply(lhs, rhs),
-                            BinaryOperator::Div(_) => ScalarData::Divide(lhs, rhs),
-                            _ => unreachable!(),
+                    data: Rc::new(match &self.operator {
+                        BinaryOperator::Mul(_) => Scalar {
+                            unit: Some(lhs_unit * &rhs_unit),
+                            data: ScalarData::Multiply(lhs, rhs),
+                        },
+                        BinaryOperator::Div(_) => Scalar {
+                            unit: Some(lhs_unit / &rhs_unit),
+                            data: ScalarData::Divide(lhs, rhs),
                         },
+                        _ => unreachable!(),
                     }),
                     node: None,
                 };
@@ -3294,6 +3383,15 @@ impl Properties {
         self.props.remove(property);
     }
 
+    /// Get a rule's weight: the `weight` property (or `default`) scaled by the `strength` tier.
+    #[must_use]
+    pub fn get_weight(&mut self, default: ProcNum) -> ProcNum {
+        let weight = self.get("weight").get_or(default);
+        let strength = self.get("strength").get_or(Strength::Normal);
+
+        weight * &strength.multiplier()
+    }
+
     /// Add a property along with a value if it is not present yet.
     pub fn add_if_not_present(
         &mut self,
@@ -3530,6 +3628,14 @@ fn create_variable_collection(
 
                 if let Some(rhs_node) = &mut rhs_node {
                     let pt_node = rhs_node.root.children.get_mut(i).and_then(Option::take);
+                    // Unpacked points are labeled with their names, unless labeled otherwise.
+                    let pt_node = pt_node.map(|mut node| {
+                        if node.root.default_label.is_empty() {
+                            node.root.default_label = pt.clone().into();
+                        }
+
+                        node
+                    });
 
                     variable_nodes.extend(pt_node.map(|x| Box::new(x) as Box<dyn Node>));
                 }
@@ -3937,7 +4043,7 @@ fn unroll_rule(
             ),
         },
         RuleOperator::Defined(op) => {
-            let weight = display.get("weight").get_or(ProcNum::one());
+            let weight = display.get_weight(ProcNum::one());
 
             let overload = match library.get_rule(op.ident.as_str()) {
                 Ok(func) => {
@@ -3948,6 +4054,7 @@ fn unroll_rule(
                             error_span: op.span,
                             function_name: op.ident.clone(),
                             params: vec![lhs.get_type(), rhs.get_type()],
+                            candidates: func.overloads.iter().map(|x| x.get_signature()).collect(),
                         });
 
                         // Pretend the rule doesn't exist.
@@ -3989,33 +4096,131 @@ fn unroll_rule_statement(
 ) -> Result<Vec<Box<dyn Node>>, Error> {
     let mut nodes = Vec::new();
 
-    let tree = IterNode::from2(&rule.lhs, &rule.rhs);
+    let tree = IterNode::new(
+        rule.rules
+            .iter()
+            .flat_map(|single| IterNode::from2(&single.lhs, &single.rhs).0)
+            .collect(),
+    );
     tree.get_iter_lengths(&mut HashMap::new(), rule.get_span())?;
 
     let mut it_index = IterTreeIterator::new(&tree);
 
     while let Some(index) = it_index.get_currents() {
-        nodes.push(unroll_rule(
-            (
-                rule.lhs
-                    .unroll(context, library, index, Properties::default()),
-                &rule.op,
-                rule.rhs
-                    .unroll(context, library, index, Properties::default()),
-            ),
+        let display = Properties::from(rule.display.clone());
+
+        if rule.rules.len() == 1 {
+            let single = rule.rules.first.as_ref();
+
+            nodes.push(unroll_rule(
+                (
+                    single
+                        .lhs
+                        .unroll(context, library, index, Properties::default()),
+                    &single.op,
+                    single
+                        .rhs
+                        .unroll(context, library, index, Properties::default()),
+                ),
+                context,
+                library,
+                rule.get_span(),
+                false,
+                display,
+            ));
+        } else {
+            nodes.push(unroll_alternative(
+                &rule.rules,
+                index,
+                context,
+                library,
+                display,
+            ));
+        }
+
+        it_index.next();
+    }
+
+    Ok(nodes)
+}
+
+/// Unroll alternative rules. At least one of them must be satisfied.
+fn unroll_alternative(
+    rules: &Punctuated<SingleRule, Vertical>,
+    index: &HashMap<u8, usize>,
+    context: &mut CompileContext,
+    library: &Library,
+    mut display: Properties,
+) -> Box<dyn Node> {
+    let weight = display.get_weight(ProcNum::one());
+    let mut node = CollectionNode::from_display(display, context);
+
+    // A single rule may unroll into multiple rules that all must hold.
+    let mut conjunctions = Vec::new();
+
+    for single in rules.iter() {
+        // Rules emitted by the operands themselves (e.g. by `collinear`) must hold regardless.
+        let lhs = single
+            .lhs
+            .unroll(context, library, index, Properties::default());
+        let rhs = single
+            .rhs
+            .unroll(context, library, index, Properties::default());
+        let count = context.rules.borrow().len();
+
+        node.push_boxed(unroll_rule(
+            (lhs, &single.op, rhs),
             context,
             library,
-            rule.get_span(),
+            single.get_span(),
             false,
-            Properties::from(rule.display.clone()),
+            Properties::default(),
         ));
 
-        it_index.next();
+        conjunctions.push(context.take_rules_since(count));
     }
 
-    Ok(nodes)
+    // `(a & b) | c` is expanded into `(a | c) & (b | c)`.
+    let mut alternatives: Vec<Vec<UnrolledRule>> = vec![Vec::new()];
+
+    for conjunction in conjunctions {
+        alternatives = alternatives
+            .iter()
+            .flat_map(|alternative| {
+                conjunction.iter().map(move |rule| {
+                    alternative
+                        .iter()
+                        .chain(std::iter::once(rule))
+                        .map(CloneWithNode::clone_without_node)
+                        .collect()
+                })
+            })
+            .collect();
+    }
+
+    for alternative in alternatives {
+        // Nested alternatives are flattened.
+        let items = alternative
+            .into_iter()
+            .flat_map(|rule| match rule.kind {
+                UnrolledRuleKind::Alternative(items) if !rule.inverted => items.0,
+                kind => vec![UnrolledRule { kind, ..rule }],
+            })
+            .collect::<Vec<_>>();
+
+        context.push_rule(UnrolledRule {
+            kind: UnrolledRuleKind::Alternative(items.into()),
+            inverted: false,
+            weight: weight.clone(),
+        });
+    }
+
+    Box::new(node)
 }
 
+/// Possible values of the `distance_literals` flag.
+const DISTANCE_LITERALS: &[&str] = &["adjust", "solve"];
+
 /// Unrolls the given script. All iterators are expanded and all conversions applied. The output can be immediately compiled.
 ///
 /// # Errors
@@ -4034,23 +4239,50 @@ pub fn unroll(input: &str) -> Result<(CompileContext, CollectionNode), Vec<Error
     let mut input = InputStream::new(&tokens);
 
     let mut statements = Vec::new();
+    let mut parse_errors = Vec::new();
 
     while !input.eof() {
-        statements.push(match input.parse() {
-            Ok(v) => v,
-            Err(err) => return Err(vec![err]),
-        });
+        let statement_start = input.clone();
+
+        match input.parse() {
+            Ok(v) => statements.push(v),
+            Err(err) => {
+                parse_errors.push(err);
+
+                // Skip the invalid statement, so that errors in the next ones are reported as well.
+                input = statement_start;
+                input.skip_statement();
+            }
+        }
+    }
+
+    // Unrolling statements after invalid ones would only result in misleading errors.
+    if !parse_errors.is_empty() {
+        return Err(parse_errors);
     }
 
     let mut flags = FlagSetConstructor::new()
         .add_set(&"optimizations", FlagSetConstructor::new())
         .add_bool_def(&"point_inequalities", true)
+        .add_bool_def(&"scale_point_inequalities", false)
+        .add_ident_def(&"distance_literals", &"adjust")
         .finish();
 
     for flag in statements.iter().filter_map(Statement::as_flag) {
         flags::set_flag(&mut flags, flag, &context);
     }
 
+    let distance_literals = &flags["distance_literals"];
+    if let Some(value) = distance_literals.as_ident() {
+        if !DISTANCE_LITERALS.contains(&value.as_str()) {
+            context.push_error(Error::EnumInvalidValue {
+                error_span: distance_literals.get_span().unwrap(),
+                available_values: DISTANCE_LITERALS,
+                received_value: value.clone(),
+            });
+        }
+    }
+
     context.flags = flags;
 
     for stat in statements {
@@ -92,6 +92,11 @@ impl CompileContext {
     pub fn take_rules(&mut self) -> Vec<UnrolledRule> {
         mem::take(&mut self.rules.borrow_mut())
     }
+
+    /// Take the rules pushed after the first `count` ones.
+    pub fn take_rules_since(&self, count: usize) -> Vec<UnrolledRule> {
+        self.rules.borrow_mut().split_off(count)
+    }
 }
 
 /// Everything related to circles.
@@ -218,6 +223,25 @@ impl CompileContext {
         expr
     }
 
+    /// Give an expression a new node made from properties and nodes, discarding the old one.
+    /// Used for expressions composed of intermediate steps that should not be displayed.
+    pub fn with_node<T: Displayed>(
+        &self,
+        mut expr: Expr<T>,
+        display: Properties,
+        nodes: Vec<Box<dyn Node>>,
+    ) -> Expr<T>
+    where
+        T::Node: FromExpr<T>,
+    {
+        let mut node = HierarchyNode::new(T::Node::from_expr(&expr, display, self));
+
+        node.extend_boxed(nodes);
+
+        expr.node = Some(node);
+        expr
+    }
+
     /// An average pointe expression with display options.
     pub fn average_p_display(
         &self,
@@ -316,10 +340,15 @@ impl CompileContext {
     }
 
     generic_expr! {intersection(k: Line, l: Line) -> Point::LineLineIntersection}
+    generic_expr! {free_point_on_line(k: Line) -> Point::OnLine}
+    generic_expr! {free_point_on_circle(omega: Circle) -> Point::OnCircle}
+    generic_expr! {fixed_point(x: Scalar, y: Scalar) -> Point::Fixed}
     generic_expr! {distance_pp(p: Point, q: Point) -> Scalar[unit::DISTANCE]::PointPointDistance}
     generic_expr! {distance_pl(p: Point, k: Line) -> Scalar[unit::DISTANCE]::PointLineDistance}
     generic_expr! {circle_center(c: Circle) -> Point::CircleCenter}
     generic_expr! {circle_radius(c: Circle) -> Scalar[unit::DISTANCE]::CircleRadius}
+    generic_expr! {point_x(p: Point) -> Scalar[unit::DISTANCE]::PointX}
+    generic_expr! {point_y(p: Point) -> Scalar[unit::DISTANCE]::PointY}
     generic_expr! {line(a: Point, b: Point) -> Line::LineFromPoints}
     generic_expr! {angle_ppp(a: Point, b: Point, c: Point) -> Scalar[unit::ANGLE]::ThreePointAngle}
     generic_expr! {angle_dir(a: Point, b: Point, c: Point) -> Scalar[unit::ANGLE]::ThreePointAngleDir}
@@ -328,8 +357,15 @@ impl CompileContext {
     generic_expr! {perpendicular_through(line: Line, point: Point) -> Line::PerpendicularThrough}
     generic_expr! {parallel_through(line: Line, point: Point) -> Line::ParallelThrough}
     generic_expr! {circle(center: Point, radius: Scalar) -> Circle::Circle}
+    generic_expr! {sin(angle: Scalar) -> Scalar[unit::SCALAR]::Sin}
+    generic_expr! {cos(angle: Scalar) -> Scalar[unit::SCALAR]::Cos}
+    generic_expr! {tan(angle: Scalar) -> Scalar[unit::SCALAR]::Tan}
     generic_expr! {add(a: Scalar, b: Scalar) -> Scalar[inferred]::Add}
     generic_expr! {sub(a: Scalar, b: Scalar) -> Scalar[inferred]::Subtract}
+    generic_expr! {add_p(p: Point, q: Point) -> Point::Add}
+    generic_expr! {sub_p(p: Point, q: Point) -> Point::Subtract}
+    generic_expr! {mult_p(p: Point, v: Scalar) -> Point::Multiply}
+    generic_expr! {rotate_p(p: Point, angle: Scalar) -> Point::Rotate}
 
     pub fn mult_display(
         &self,
@@ -372,6 +408,22 @@ impl CompileContext {
     pub fn div(&self, a: Expr<Scalar>, b: Expr<Scalar>) -> Expr<Scalar> {
         self.div_display(a, b, Properties::default())
     }
+
+    pub fn abs_display(&self, mut v: Expr<Scalar>, display: Properties) -> Expr<Scalar> {
+        let nodes = take_nodes!(v);
+        self.expr_with(
+            Scalar {
+                unit: v.data.unit,
+                data: ScalarData::Abs(v),
+            },
+            display,
+            nodes,
+        )
+    }
+
+    pub fn abs(&self, v: Expr<Scalar>) -> Expr<Scalar> {
+        self.abs_display(v, Properties::default())
+    }
 }
 
 /// Helper macro for general rule functions.
@@ -414,7 +466,7 @@ impl CompileContext {
         mut display: Properties,
         def_weight: ProcNum,
     ) -> Box<dyn Node> {
-        let weight = display.get("weight").get_or(def_weight);
+        let weight = display.get_weight(def_weight);
         let mut node = CollectionNode::from_display(display, self);
 
         node.extend(lhs);
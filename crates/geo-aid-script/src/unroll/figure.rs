@@ -5,9 +5,12 @@ use crate::math::Build;
 use crate::{
     figure::SpannedMathString as MathString,
     parser::{FromProperty, Parse, PropertyValue},
-    span, Error,
+    span,
+    token::number::ProcNum,
+    Error,
 };
-use geo_aid_figure::Style;
+use geo_aid_figure::{Color, Style};
+use num_traits::{FromPrimitive, One};
 use std::{collections::HashMap, fmt::Debug, ops::Deref};
 
 use super::{
@@ -284,6 +287,12 @@ pub enum AssociatedData {
     Style(MaybeUnset<Style>),
     /// A line type - line, ray or segment.
     LineType(MaybeUnset<LineType>),
+    /// A small count, e.g. the number of arcs marking an angle.
+    Count(MaybeUnset<u8>),
+    /// An optional color
+    Color(Option<Color>),
+    /// A label
+    Label(MathString),
 }
 
 impl AssociatedData {
@@ -310,6 +319,30 @@ impl AssociatedData {
             _ => None,
         }
     }
+
+    #[must_use]
+    pub fn as_count(&self) -> Option<MaybeUnset<u8>> {
+        match self {
+            Self::Count(v) => Some(v.copied()),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_color(&self) -> Option<Option<Color>> {
+        match self {
+            Self::Color(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_label(&self) -> Option<&MathString> {
+        match self {
+            Self::Label(v) => Some(v),
+            _ => None,
+        }
+    }
 }
 
 impl From<MaybeUnset<bool>> for AssociatedData {
@@ -330,6 +363,24 @@ impl From<MaybeUnset<LineType>> for AssociatedData {
     }
 }
 
+impl From<MaybeUnset<u8>> for AssociatedData {
+    fn from(value: MaybeUnset<u8>) -> Self {
+        Self::Count(value)
+    }
+}
+
+impl From<Option<Color>> for AssociatedData {
+    fn from(value: Option<Color>) -> Self {
+        Self::Color(value)
+    }
+}
+
+impl From<MathString> for AssociatedData {
+    fn from(value: MathString) -> Self {
+        Self::Label(value)
+    }
+}
+
 /// Contains a root node, apart from its children. Simulates a hierarchy.
 #[derive(Debug)]
 pub struct HierarchyNode<T: Node> {
@@ -760,6 +811,10 @@ pub struct PointNode {
     pub display_label: MaybeUnset<bool>,
     /// Whether to display the point's dot.
     pub display_dot: MaybeUnset<bool>,
+    /// The dot's size, relative to the default
+    pub size: ProcNum,
+    /// The point's color
+    pub color: Option<Color>,
     /// Default label to use if `label` is empty.
     pub default_label: MathString,
     /// Defining expression
@@ -773,6 +828,8 @@ impl Dummy for PointNode {
             label: MaybeUnset::new(MathString::new(span!(0, 0, 0, 0))),
             display_label: MaybeUnset::new(true),
             display_dot: MaybeUnset::new(true),
+            size: ProcNum::one(),
+            color: None,
             default_label: MathString::new(span!(0, 0, 0, 0)),
             expr: Expr::dummy(),
         }
@@ -809,6 +866,8 @@ impl Node for PointNode {
                 }
                 .string,
                 display_dot: self.display_dot.unwrap(),
+                size: self.size.to_complex().real,
+                color: self.color,
             });
         }
     }
@@ -823,6 +882,8 @@ impl FromExpr<Point> for PointNode {
                 .maybe_unset(MathString::new(span!(0, 0, 0, 0))),
             display_label: props.get("display_label").maybe_unset(true),
             display_dot: props.get("display_dot").maybe_unset(true),
+            size: props.get("size").get_or(ProcNum::one()),
+            color: props.get("color").get(),
             default_label: props
                 .get("default-label")
                 .ok_or(MathString::new(span!(0, 0, 0, 0))),
@@ -848,6 +909,8 @@ pub struct CircleNode {
     pub default_label: MathString,
     /// How to draw the circle (brush)
     pub style: MaybeUnset<Style>,
+    /// The circle's color
+    pub color: Option<Color>,
     /// The defining expression
     pub expr: Expr<Circle>,
 }
@@ -860,6 +923,7 @@ impl Dummy for CircleNode {
             display_label: MaybeUnset::new(true),
             default_label: MathString::new(span!(0, 0, 0, 0)),
             style: MaybeUnset::new(Style::default()),
+            color: None,
             expr: Expr::dummy(),
         }
     }
@@ -895,6 +959,7 @@ impl Node for CircleNode {
                 }
                 .string,
                 style: self.style.unwrap(),
+                color: self.color,
             });
         }
     }
@@ -912,6 +977,7 @@ impl FromExpr<Circle> for CircleNode {
                 .get("default-label")
                 .ok_or(MathString::new(span!(0, 0, 0, 0))),
             style: props.get("style").maybe_unset(Style::default()),
+            color: props.get("color").get(),
             expr: expr.clone_without_node(),
         };
 
@@ -973,12 +1039,35 @@ property_enum! {
     }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for LineType {
     fn default() -> Self {
         Self::Line
     }
 }
 
+property_enum! {
+    Strength {
+        Soft: "soft",
+        Normal: "normal",
+        Hard: "hard"
+    }
+}
+
+impl Strength {
+    /// The factor a rule's weight is multiplied by.
+    #[must_use]
+    pub fn multiplier(self) -> ProcNum {
+        let ten = ProcNum::from_i32(10).unwrap();
+
+        match self {
+            Self::Soft => ProcNum::one() / &ten,
+            Self::Normal => ProcNum::one(),
+            Self::Hard => ten,
+        }
+    }
+}
+
 property_enum_impl! {
     Style {
         Solid: "solid",
@@ -988,6 +1077,73 @@ property_enum_impl! {
     }
 }
 
+/// Names of colors that can be used without a hex code.
+pub const COLOR_NAMES: &[&str] = &[
+    "black", "white", "gray", "red", "green", "blue", "yellow", "orange", "purple", "brown",
+    "cyan", "magenta",
+];
+
+/// Get the color with the given name.
+fn named_color(name: &str) -> Option<Color> {
+    let (r, g, b) = match name {
+        "black" => (0, 0, 0),
+        "white" => (255, 255, 255),
+        "gray" => (128, 128, 128),
+        "red" => (255, 0, 0),
+        "green" => (0, 128, 0),
+        "blue" => (0, 0, 255),
+        "yellow" => (255, 255, 0),
+        "orange" => (255, 165, 0),
+        "purple" => (128, 0, 128),
+        "brown" => (165, 42, 42),
+        "cyan" => (0, 255, 255),
+        "magenta" => (255, 0, 255),
+        _ => return None,
+    };
+
+    Some(Color { r, g, b })
+}
+
+/// Parse a color given as a name or as a `#rrggbb` hex code.
+fn parse_color(value: &str) -> Option<Color> {
+    let value = value.to_lowercase();
+
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+
+        let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+        Some(Color {
+            r: component(0)?,
+            g: component(2)?,
+            b: component(4)?,
+        })
+    } else {
+        named_color(&value)
+    }
+}
+
+impl FromProperty for Color {
+    fn from_property(property: PropertyValue) -> Result<Self, Error> {
+        match property {
+            PropertyValue::Number(n) => Err(Error::InvalidColor {
+                error_span: n.get_span(),
+            }),
+            PropertyValue::Ident(i) => parse_color(&i.to_string()).ok_or(Error::InvalidColor {
+                error_span: i.get_span(),
+            }),
+            PropertyValue::String(s) => parse_color(&s.content).ok_or(Error::InvalidColor {
+                error_span: s.get_span(),
+            }),
+            PropertyValue::RawString(s) => Err(Error::NonRawStringOrIdentExpected {
+                error_span: s.get_span(),
+            }),
+        }
+    }
+}
+
 /// Node for a line
 #[derive(Debug)]
 pub struct LineNode {
@@ -1003,6 +1159,10 @@ pub struct LineNode {
     pub line_type: MaybeUnset<LineType>,
     /// How to draw the line (brush)
     pub style: MaybeUnset<Style>,
+    /// The line's color
+    pub color: Option<Color>,
+    /// Whether to draw an arrowhead (only for rays and segments)
+    pub arrow: MaybeUnset<bool>,
     /// Defining expression
     pub expr: Expr<Line>,
 }
@@ -1016,6 +1176,8 @@ impl Dummy for LineNode {
             default_label: MathString::new(span!(0, 0, 0, 0)),
             line_type: MaybeUnset::new(LineType::Line),
             style: MaybeUnset::new(Style::default()),
+            color: None,
+            arrow: MaybeUnset::new(false),
             expr: Expr::dummy(),
         }
     }
@@ -1048,6 +1210,8 @@ impl Node for LineNode {
                 MathString::new(span!(0, 0, 0, 0))
             };
             let style = self.style.unwrap();
+            let color = self.color;
+            let arrow = self.arrow.unwrap();
 
             match self.line_type.unwrap() {
                 LineType::Line => {
@@ -1056,6 +1220,7 @@ impl Node for LineNode {
                         id,
                         label: label.string,
                         style,
+                        color,
                     });
                 }
                 LineType::Ray => match &self.expr.data.as_ref() {
@@ -1067,6 +1232,8 @@ impl Node for LineNode {
                             q_id,
                             label: label.string,
                             style,
+                            color,
+                            arrow,
                         });
                     }
                     Line::AngleBisector(a, b, c) => {
@@ -1085,6 +1252,8 @@ impl Node for LineNode {
                             q_id,
                             label: label.string,
                             style,
+                            color,
+                            arrow,
                         });
                     }
                     _ => unreachable!(),
@@ -1098,6 +1267,8 @@ impl Node for LineNode {
                             q_id,
                             label: label.string,
                             style,
+                            color,
+                            arrow,
                         });
                     }
                     _ => unreachable!(),
@@ -1120,6 +1291,8 @@ impl FromExpr<Line> for LineNode {
                 .ok_or(MathString::new(span!(0, 0, 0, 0))),
             line_type: MaybeUnset::new(LineType::Line),
             style: props.get("style").maybe_unset(Style::default()),
+            color: props.get("color").get(),
+            arrow: props.get("arrow").maybe_unset(false),
             expr: expr.clone_without_node(),
         };
 
@@ -11,30 +11,60 @@ use num_rational::Ratio;
 
 use crate::{
     parser::Type,
-    token::number::ProcNum,
+    token::{number::ProcNum, Span},
     unit,
     unroll::{AnyExpr, Expr, GeoType, PointCollection, Scalar},
-    ComplexUnit,
+    ComplexUnit, Error,
 };
 
 use super::{
     context::CompileContext, figure::Node, most_similar, Convert, ConvertFrom, Properties,
 };
 
+pub mod abs;
 pub mod angle;
+pub mod area;
+pub mod between;
 pub mod bisector;
+pub mod ccw;
+pub mod center;
+pub mod centroid;
 pub mod circle;
+pub mod circumcircle;
+pub mod collinear;
+pub mod concyclic;
+pub mod coordinates;
 pub mod degrees;
+pub mod dilate;
 pub mod dst;
+pub mod fixed;
+pub mod foot;
+pub mod in_range;
+pub mod incircle;
 pub mod intersection;
 pub mod lies_on;
 pub mod line;
 pub mod mid;
+pub mod minmax;
+pub mod nine_point;
+pub mod number;
+pub mod orthocenter;
 pub mod parallel;
 pub mod perpendicular;
 pub mod point;
+pub mod polygon;
+pub mod power;
 pub mod radians;
+pub mod radius;
+pub mod ray;
+pub mod reflect;
+pub mod rotate;
 pub mod segment;
+pub mod side;
+pub mod tangent;
+pub mod translate;
+pub mod trigonometry;
+pub mod vector;
 
 /// A prelude for builtin functions.
 pub mod prelude {
@@ -46,12 +76,12 @@ pub mod prelude {
                 BuildAssociated, BundleNode, CollectionNode, HierarchyNode, LineNode, LineType,
                 PointNode, ScalarNode,
             },
-            library::{macros::*, Angle, Distance, Function, Library, Pc, Rule, Unitless},
+            library::{macros::*, Angle, Area, Distance, Function, Library, Pc, Rule, Unitless},
             Bundle, Circle, CloneWithNode, Expr, GeoType, Line, Point, Properties, ScalarData,
             UnrolledRule, UnrolledRuleKind,
         },
     };
-    pub(crate) use geo_aid_figure::Style;
+    pub(crate) use geo_aid_figure::{Color, Style};
 }
 /// A `GeoScript` function.
 pub struct Function {
@@ -118,6 +148,17 @@ pub trait Overload {
     #[must_use]
     fn get_returned_type(&self, params: &[AnyExpr]) -> Option<Type>;
 
+    /// Get a human-readable list of the parameters this overload accepts, e.g. `(Point, Point)`.
+    #[must_use]
+    fn get_signature(&self) -> String;
+
+    /// Get an error explaining why the overload cannot be called with the given parameters,
+    /// if there's a more specific one than the overload not being found.
+    #[must_use]
+    fn get_mismatch_error(&self, _params: &[AnyExpr], _error_span: Span) -> Option<Error> {
+        None
+    }
+
     /// Unroll the function for the given params. The resulting expression
     /// matches the type returned by `get_returned_type`.
     #[must_use]
@@ -142,6 +183,18 @@ macro_rules! tuple_size {
     }
 }
 
+/// Format parameter types as a signature, e.g. `(Point, Point)`.
+fn format_signature(types: &[Type]) -> String {
+    format!(
+        "({})",
+        types
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
 macro_rules! impl_overload_function {
     ($($arg:ident),* $(,)?) => {
         impl<$($arg,)* R, F> Overload for FunctionOverload<F, ($($arg,)*), R, &mut CompileContext>
@@ -164,6 +217,10 @@ macro_rules! impl_overload_function {
                 }
             }
 
+            fn get_signature(&self) -> String {
+                format_signature(&[$($arg::get_type()),*])
+            }
+
             fn unroll(&self, params: Vec<AnyExpr>, context: &mut CompileContext, props: Properties) -> AnyExpr {
                 #[allow(unused_mut, unused_variables)]
                 let mut param = params.into_iter();
@@ -194,6 +251,10 @@ macro_rules! impl_overload_function {
                 }
             }
 
+            fn get_signature(&self) -> String {
+                format_signature(&[$($arg::get_type()),*])
+            }
+
             fn unroll(&self, params: Vec<AnyExpr>, context: &mut CompileContext, props: Properties) -> AnyExpr {
                 #[allow(unused_mut, unused_variables)]
                 let mut param = params.into_iter();
@@ -327,6 +388,10 @@ pub trait RuleOverload {
     #[must_use]
     fn matches(&self, lhs: &AnyExpr, rhs: &AnyExpr) -> bool;
 
+    /// Get a human-readable list of the operands this overload accepts, e.g. `(Point, Point)`.
+    #[must_use]
+    fn get_signature(&self) -> String;
+
     /// Unroll this rule.
     #[must_use]
     fn unroll(
@@ -383,6 +448,10 @@ impl<
         lhs.can_convert_to(L::get_type()) && rhs.can_convert_to(R::get_type())
     }
 
+    fn get_signature(&self) -> String {
+        format_signature(&[L::get_type(), R::get_type()])
+    }
+
     fn unroll(
         &self,
         lhs: AnyExpr,
@@ -432,20 +501,50 @@ impl Library {
         };
 
         point::register(&mut library); // Point()
+        fixed::register(&mut library); // fixed()
+        foot::register(&mut library); // foot()
+        number::register(&mut library); // number()
         dst::register(&mut library); // dst()
-        angle::register(&mut library); // angle()
+        angle::register(&mut library); // angle(), dir_angle()
+        area::register(&mut library); // area()
+        ccw::register(&mut library); // ccw()
         degrees::register(&mut library); // degrees()
         radians::register(&mut library); // radians()
+        trigonometry::register(&mut library); // sin(), cos(), tan()
+        power::register(&mut library); // sqrt(), pow()
+        abs::register(&mut library); // abs()
         mid::register(&mut library); // mid()
+        minmax::register(&mut library); // min(), max()
+        in_range::register(&mut library); // in_range()
+        centroid::register(&mut library); // centroid()
         perpendicular::register(&mut library); // perpendicular_through()
         parallel::register(&mut library); // parallel_through()
         intersection::register(&mut library); // intersection()
-        bisector::register(&mut library); // bisector()
+        bisector::register(&mut library); // bisector(), external_bisector()
         circle::register(&mut library); // Circle()
+        circumcircle::register(&mut library); // circumcenter(), circumcircle()
+        nine_point::register(&mut library); // nine_point_circle()
+        radius::register(&mut library); // radius()
+        center::register(&mut library); // center()
+        coordinates::register(&mut library); // x(), y()
+        incircle::register(&mut library); // incenter(), incircle()
+        orthocenter::register(&mut library); // orthocenter()
         segment::register(&mut library); // Segment()
+        polygon::register(&mut library); // polygon()
+        ray::register(&mut library); // Ray()
+        vector::register(&mut library); // vec()
         line::register(&mut library); // Line()
+        reflect::register(&mut library); // reflect()
+        translate::register(&mut library); // translate()
+        dilate::register(&mut library); // dilate()
+        rotate::register(&mut library); // rotate()
+        tangent::register(&mut library); // tangent(), tangent_at()
+        collinear::register(&mut library); // collinear()
+        between::register(&mut library); // between()
+        concyclic::register(&mut library); // concyclic()
 
         lies_on::register(&mut library); // lies_on
+        side::register(&mut library); // same_side, opposite_side
 
         library
     }
@@ -534,6 +633,12 @@ impl<const N: usize> From<Expr<PointCollection>> for Pc<N> {
     }
 }
 
+impl<const N: usize> From<Pc<N>> for AnyExpr {
+    fn from(value: Pc<N>) -> Self {
+        value.0.into()
+    }
+}
+
 impl<const N: usize> Deref for Pc<N> {
     type Target = Expr<PointCollection>;
 
@@ -614,6 +719,7 @@ impl<const DST_NUM: i64, const DST_DENOM: i64, const ANG_NUM: i64, const ANG_DEN
 pub type Distance = ScalarUnit<1, 1, 0, 1>;
 pub type Angle = ScalarUnit<0, 1, 1, 1>;
 pub type Unitless = ScalarUnit<0, 1, 0, 1>;
+pub type Area = ScalarUnit<2, 1, 0, 1>;
 
 /// Returns what size of point collection can the given bundle type be cast onto.
 /// 0 signifies that casting is not possible
@@ -0,0 +1,41 @@
+//! The `abs` function
+
+use crate::{
+    parser::Type,
+    unroll::{AnyExpr, Convert},
+};
+
+use super::{prelude::*, Overload};
+
+/// `abs(value)` - absolute value of a scalar of any unit. Points are not accepted.
+struct AbsScalar;
+
+impl Overload for AbsScalar {
+    fn get_returned_type(&self, params: &[AnyExpr]) -> Option<Type> {
+        if params.len() == 1 {
+            params[0].can_convert_to_scalar(None).map(Type::Scalar)
+        } else {
+            None
+        }
+    }
+
+    fn get_signature(&self) -> String {
+        String::from("(Scalar (any unit))")
+    }
+
+    fn unroll(
+        &self,
+        mut params: Vec<AnyExpr>,
+        context: &mut CompileContext,
+        props: Properties,
+    ) -> AnyExpr {
+        let value = params.swap_remove(0).convert(context);
+
+        context.abs_display(value, props).into()
+    }
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(Function::new("abs").overload(AbsScalar));
+}
@@ -1,7 +1,8 @@
 /// The `angle` function
 use crate::{
-    figure::{LineItem, RayItem, SegmentItem},
+    figure::{AngleDefinition, AngleItem, LineItem, RayItem, SegmentItem, SpannedMathString},
     math::Build,
+    span,
 };
 
 use super::prelude::*;
@@ -9,20 +10,57 @@ use geo_aid_figure::math_string::MathString;
 
 /// angle(point, point, point) - angle delimited by 3 points.
 fn angle_function_ppp(
+    a: Expr<Point>,
+    b: Expr<Point>,
+    c: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Angle {
+    angle_ppp(a, b, c, context, display, false)
+}
+
+/// dir_angle(point, point, point) - directed angle delimited by 3 points.
+fn dir_angle_function_ppp(
+    a: Expr<Point>,
+    b: Expr<Point>,
+    c: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Angle {
+    angle_ppp(a, b, c, context, display, true)
+}
+
+/// Helper function for the point versions of `angle` and `dir_angle`.
+fn angle_ppp(
     a: Expr<Point>,
     b: Expr<Point>,
     c: Expr<Point>,
     context: &CompileContext,
     mut display: Properties,
+    directed: bool,
 ) -> Angle {
     let display_arms = display.get("display_arms").maybe_unset(true);
+    let display_arc = display.get("display_arc").maybe_unset(false);
+    let arcs = display.get("arcs").maybe_unset(1_u8);
+    let color: Option<Color> = display.get("color").get();
+    let label = display
+        .get("label")
+        .ok_or(SpannedMathString::new(span!(0, 0, 0, 0)));
     let arms_style = display.get("arms_style").maybe_unset(Style::default());
     let arms_type = display.get("arms_type").maybe_unset(LineType::Segment);
 
-    let mut expr = context.angle_ppp_display(a, b, c, display);
+    let mut expr = if directed {
+        context.angle_dir_display(a, b, c, display)
+    } else {
+        context.angle_ppp_display(a, b, c, display)
+    };
 
     if let Some(node) = &mut expr.node {
         node.insert_data("display_arms", display_arms);
+        node.insert_data("display_arc", display_arc);
+        node.insert_data("arcs", arcs);
+        node.insert_data("color", color);
+        node.insert_data("label", label);
         node.insert_data("arms_style", arms_style);
         node.insert_data("arms_type", arms_type);
 
@@ -32,10 +70,14 @@ fn angle_function_ppp(
 }
 
 /// ```
-/// # use geo_aid_figure::Style;
+/// # use geo_aid_figure::{math_string::MathString, Color, Style};
 /// # use geo_aid_script::unroll::figure::LineType;
 /// struct Associated {
 ///     display_arms: bool,
+///     display_arc: bool,
+///     arcs: u8,
+///     color: Option<Color>,
+///     label: MathString,
 ///     arms_style: Style,
 ///     amrs_type: LineType
 /// }
@@ -72,12 +114,14 @@ pub fn display_angle_arms(
                 id,
                 label: MathString::new(),
                 style: arms_style,
+                color: None,
             });
             let id = build.load(&line_c);
             build.add(LineItem {
                 id,
                 label: MathString::new(),
                 style: arms_style,
+                color: None,
             });
         }
         LineType::Ray => {
@@ -86,12 +130,16 @@ pub fn display_angle_arms(
                 q_id: a,
                 label: MathString::new(),
                 style: arms_style,
+                color: None,
+                arrow: false,
             });
             build.add(RayItem {
                 p_id: b,
                 q_id: c,
                 label: MathString::new(),
                 style: arms_style,
+                color: None,
+                arrow: false,
             });
         }
         LineType::Segment => {
@@ -100,12 +148,16 @@ pub fn display_angle_arms(
                 q_id: a,
                 label: MathString::new(),
                 style: arms_style,
+                color: None,
+                arrow: false,
             });
             build.add(SegmentItem {
                 p_id: b,
                 q_id: c,
                 label: MathString::new(),
                 style: arms_style,
+                color: None,
+                arrow: false,
             });
         }
     }
@@ -117,34 +169,81 @@ impl BuildAssociated<ScalarNode> for Associated {
         build: &mut Build,
         associated: &mut HierarchyNode<ScalarNode>,
     ) {
-        let display_arms = associated
-            .get_data("display_arms")
+        let display_arc = associated
+            .get_data("display_arc")
             .unwrap()
             .as_bool()
             .unwrap()
             .unwrap();
 
-        let arms_style = associated
-            .get_data("arms_style")
-            .unwrap()
-            .as_style()
-            .unwrap()
-            .unwrap();
-
-        let arms_type = associated
-            .get_data("arms_type")
-            .unwrap()
-            .as_line_type()
-            .unwrap()
-            .unwrap();
-
-        if display_arms {
-            match &associated.root.expr.data.data {
+        if display_arc {
+            let definition = match &associated.root.expr.data.data {
                 ScalarData::ThreePointAngle(a_expr, b_expr, c_expr)
                 | ScalarData::ThreePointAngleDir(a_expr, b_expr, c_expr) => {
-                    display_angle_arms(build, a_expr, b_expr, c_expr, arms_type, arms_style);
+                    AngleDefinition::ThreePoint(
+                        build.load(a_expr),
+                        build.load(b_expr),
+                        build.load(c_expr),
+                    )
+                }
+                ScalarData::TwoLineAngle(k_expr, l_expr) => {
+                    AngleDefinition::TwoLine(build.load(k_expr), build.load(l_expr))
                 }
                 _ => unreachable!(),
+            };
+            let id = build.load(&associated.root.expr);
+            let arcs = associated
+                .get_data("arcs")
+                .unwrap()
+                .as_count()
+                .unwrap()
+                .unwrap();
+            let color = associated.get_data("color").unwrap().as_color().unwrap();
+            let label = associated.get_data("label").unwrap().as_label().unwrap();
+            let directed = matches!(
+                associated.root.expr.data.data,
+                ScalarData::ThreePointAngleDir(..)
+            );
+
+            build.add(AngleItem {
+                id,
+                definition,
+                directed,
+                arcs,
+                label: label.string.clone(),
+                style: Style::default(),
+                color,
+            });
+        }
+
+        // Only angles defined by points have arms.
+        if let ScalarData::ThreePointAngle(a_expr, b_expr, c_expr)
+        | ScalarData::ThreePointAngleDir(a_expr, b_expr, c_expr) =
+            &associated.root.expr.data.data
+        {
+            let display_arms = associated
+                .get_data("display_arms")
+                .unwrap()
+                .as_bool()
+                .unwrap()
+                .unwrap();
+
+            let arms_style = associated
+                .get_data("arms_style")
+                .unwrap()
+                .as_style()
+                .unwrap()
+                .unwrap();
+
+            let arms_type = associated
+                .get_data("arms_type")
+                .unwrap()
+                .as_line_type()
+                .unwrap()
+                .unwrap();
+
+            if display_arms {
+                display_angle_arms(build, a_expr, b_expr, c_expr, arms_type, arms_style);
             }
         }
     }
@@ -155,9 +254,26 @@ fn angle_function_ll(
     k: Expr<Line>,
     l: Expr<Line>,
     context: &CompileContext,
-    display: Properties,
+    mut display: Properties,
 ) -> Angle {
-    context.angle_ll_display(k, l, display).into()
+    let display_arc = display.get("display_arc").maybe_unset(false);
+    let arcs = display.get("arcs").maybe_unset(1_u8);
+    let color: Option<Color> = display.get("color").get();
+    let label = display
+        .get("label")
+        .ok_or(SpannedMathString::new(span!(0, 0, 0, 0)));
+
+    let mut expr = context.angle_ll_display(k, l, display);
+
+    if let Some(node) = &mut expr.node {
+        node.insert_data("display_arc", display_arc);
+        node.insert_data("arcs", arcs);
+        node.insert_data("color", color);
+        node.insert_data("label", label);
+
+        node.set_associated(Associated);
+    }
+    expr.into()
 }
 
 /// Register the function
@@ -176,4 +292,18 @@ pub fn register(library: &mut Library) {
             .overload(angle_function_ppp)
             .overload(angle_function_ll),
     );
+
+    library.add(
+        Function::new("dir_angle")
+            .overload(|mut col: Pc<3>, context: &CompileContext, display| {
+                dir_angle_function_ppp(
+                    index!(node col,0),
+                    index!(node col,1),
+                    index!(node col,2),
+                    context,
+                    display,
+                )
+            })
+            .overload(dir_angle_function_ppp),
+    );
 }
@@ -0,0 +1,66 @@
+//! The `area` function
+
+use num_traits::FromPrimitive;
+
+use super::prelude::*;
+use crate::{token::number::ProcNum, unroll::Scalar};
+
+/// The signed area of a triangle, that is half of the cross product `(B - A) x (C - A)`.
+/// Positive if `A`, `B` and `C` are in counterclockwise order.
+pub fn signed_area(
+    a: Expr<Point>,
+    b: Expr<Point>,
+    c: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Scalar> {
+    let ab_x = context.sub(
+        context.point_x(b.clone_without_node()),
+        context.point_x(a.clone_without_node()),
+    );
+    let ab_y = context.sub(context.point_y(b), context.point_y(a.clone_without_node()));
+    let ac_x = context.sub(
+        context.point_x(c.clone_without_node()),
+        context.point_x(a.clone_without_node()),
+    );
+    let ac_y = context.sub(context.point_y(c), context.point_y(a));
+
+    let cross = context.sub(context.mult(ab_x, ac_y), context.mult(ab_y, ac_x));
+
+    context.div_display(
+        cross,
+        number!(SCALAR ProcNum::from_i32(2).unwrap()),
+        display,
+    )
+}
+
+/// `area(point, point, point)` - the area of a triangle, the absolute value of its signed area.
+fn area_function_point_point_point(
+    a: Expr<Point>,
+    b: Expr<Point>,
+    c: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Area {
+    Area::from(context.abs_display(
+        signed_area(a, b, c, context, Properties::default()),
+        display,
+    ))
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("area")
+            .overload(|mut col: Pc<3>, context: &CompileContext, display| {
+                area_function_point_point_point(
+                    index!(node col, 0),
+                    index!(node col, 1),
+                    index!(node col, 2),
+                    context,
+                    display,
+                )
+            })
+            .overload(area_function_point_point_point),
+    );
+}
@@ -0,0 +1,50 @@
+//! The `between` function
+
+use num_traits::One;
+
+use super::prelude::*;
+use crate::take_nodes;
+use crate::token::number::ProcNum;
+
+/// `between(A, X, B)` - makes `X` lie on segment `AB` and returns it. Expressed as
+/// `AX + XB = AB`, which, unlike collinearity, keeps `X` between the ends.
+fn between_function_point_point_point(
+    mut a: Expr<Point>,
+    mut x: Expr<Point>,
+    mut b: Expr<Point>,
+    context: &mut CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    let nodes = take_nodes!(a, x, b);
+
+    context.push_rule(UnrolledRule {
+        kind: UnrolledRuleKind::ScalarEq(
+            context.add(
+                context.distance_pp(a.clone_without_node(), x.clone_without_node()),
+                context.distance_pp(x.clone_without_node(), b.clone_without_node()),
+            ),
+            context.distance_pp(a, b),
+        ),
+        inverted: false,
+        weight: ProcNum::one(),
+    });
+
+    context.with_node(x, display, nodes)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("between")
+            .overload(|mut col: Pc<3>, context: &mut CompileContext, display| {
+                between_function_point_point_point(
+                    index!(node col, 0),
+                    index!(node col, 1),
+                    index!(node col, 2),
+                    context,
+                    display,
+                )
+            })
+            .overload(between_function_point_point_point),
+    );
+}
@@ -1,6 +1,7 @@
-//! The `bisector` function
+//! The `bisector` and `external_bisector` functions
 
 use crate::math::Build;
+use crate::take_nodes;
 
 use super::{angle::display_angle_arms, prelude::*};
 
@@ -105,8 +106,40 @@ pub fn point_point(
     )
 }
 
-/// Register the function
+/// external_bisector(point, point, point) - an angle's external bisector.
+pub fn external_point_point_point(
+    mut a: Expr<Point>,
+    mut b: Expr<Point>,
+    mut c: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Line> {
+    let nodes = take_nodes!(a, b, c);
+    // The external bisector is perpendicular to the internal one at the vertex.
+    // Math IR only collapses perpendiculars of perpendicular or parallel lines,
+    // so the bisector is kept as-is.
+    let b_clone = b.clone_without_node();
+    let line = context.perpendicular_through(context.bisector_ppp(a, b, c), b_clone);
+
+    context.with_node(line, display, nodes)
+}
+
+/// Register the functions
 pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("external_bisector")
+            .overload(|mut col: Pc<3>, context: &CompileContext, display| {
+                external_point_point_point(
+                    index!(node col,0),
+                    index!(node col,1),
+                    index!(node col,2),
+                    context,
+                    display,
+                )
+            })
+            .overload(external_point_point_point),
+    );
+
     library.add(
         Function::new("bisector")
             .overload(|mut col: Pc<3>, context: &CompileContext, display| {
@@ -0,0 +1,47 @@
+//! The `ccw` function
+//!
+//! Unlike `same_side` and `opposite_side`, this is a function and not a rule operator.
+//! Rule operators are always binary (`lhs op rhs`), while orientation is a property of
+//! three points that doesn't split naturally into two operands.
+
+use num_traits::{One, Zero};
+
+use super::{area::signed_area, prelude::*};
+use crate::token::number::ProcNum;
+
+/// `ccw(A, B, C)` - makes the triangle `ABC` counterclockwise and returns its signed area.
+fn ccw_function_point_point_point(
+    a: Expr<Point>,
+    b: Expr<Point>,
+    c: Expr<Point>,
+    context: &mut CompileContext,
+    mut display: Properties,
+) -> Area {
+    let weight = display.get_weight(ProcNum::one());
+    let area = signed_area(a, b, c, context, display);
+
+    context.push_rule(UnrolledRule {
+        kind: UnrolledRuleKind::Gt(area.clone_without_node(), number!(SCALAR ProcNum::zero())),
+        inverted: false,
+        weight,
+    });
+
+    Area::from(area)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("ccw")
+            .overload(|mut col: Pc<3>, context: &mut CompileContext, display| {
+                ccw_function_point_point_point(
+                    index!(node col, 0),
+                    index!(node col, 1),
+                    index!(node col, 2),
+                    context,
+                    display,
+                )
+            })
+            .overload(ccw_function_point_point_point),
+    );
+}
@@ -0,0 +1,17 @@
+//! The `center` function
+
+use super::prelude::*;
+
+/// `center(circle)` - the center of a circle.
+fn center_function_circle(
+    circle: Expr<Circle>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    context.circle_center_display(circle, display)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(Function::new("center").overload(center_function_circle));
+}
@@ -0,0 +1,19 @@
+//! The `centroid` function
+
+use super::{mid::MidPoint, prelude::*};
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("centroid")
+            .overload(|mut col: Pc<0>, context: &CompileContext, props| {
+                context.average_p_display(
+                    (0..col.0.data.length)
+                        .map(|i| index!(node col, i))
+                        .collect(),
+                    props,
+                )
+            })
+            .overload(MidPoint),
+    );
+}
@@ -1,6 +1,6 @@
 //! The circle function
 
-use super::{prelude::*, ScalarUnit};
+use super::{circumcircle::circumcircle, prelude::*, ScalarUnit};
 
 /// Circle constructor. Creates a circle based off of its center and radius.
 fn circle_function(
@@ -12,6 +12,18 @@ fn circle_function(
     context.circle_display(center, radius.0, display)
 }
 
+/// Circle constructor. Creates a circle based off of its center and a point it passes through.
+fn circle_function_through(
+    center: Expr<Point>,
+    through: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Circle> {
+    let radius = context.distance_pp(center.clone_without_node(), through);
+
+    context.circle_display(center, radius, display)
+}
+
 /// Register the function
 pub fn register(library: &mut Library) {
     library.add(
@@ -22,6 +34,17 @@ pub fn register(library: &mut Library) {
                     circle_function(center, radius, context, display)
                 },
             )
+            .overload(circle_function_through)
+            .overload(|mut col: Pc<3>, context: &CompileContext, display| {
+                circumcircle(
+                    index!(node col, 0),
+                    index!(node col, 1),
+                    index!(node col, 2),
+                    context,
+                    display,
+                )
+            })
+            .overload(circumcircle)
             .overload(|context: &mut CompileContext, display| {
                 let mut center = context.free_point();
                 let mut radius = context.free_scalar();
@@ -0,0 +1,78 @@
+//! The `circumcenter` and `circumcircle` functions
+
+use super::prelude::*;
+use crate::take_nodes;
+
+/// Perpendicular bisector of a segment, with no display.
+fn perpendicular_bisector(a: Expr<Point>, b: Expr<Point>, context: &CompileContext) -> Expr<Line> {
+    context.perpendicular_through(
+        context.line(a.clone_without_node(), b.clone_without_node()),
+        context.average_p(vec![a, b]),
+    )
+}
+
+/// `circumcenter(point, point, point)` - the center of the circle circumscribed on a triangle.
+pub fn circumcenter(
+    mut a: Expr<Point>,
+    mut b: Expr<Point>,
+    mut c: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    let nodes = take_nodes!(a, b, c);
+    let b_clone = b.clone_without_node();
+
+    let center = context.intersection(
+        perpendicular_bisector(a, b, context),
+        perpendicular_bisector(b_clone, c, context),
+    );
+
+    context.with_node(center, display, nodes)
+}
+
+/// `circumcircle(point, point, point)` - the circle circumscribed on a triangle.
+pub fn circumcircle(
+    mut a: Expr<Point>,
+    mut b: Expr<Point>,
+    mut c: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Circle> {
+    let nodes = take_nodes!(a, b, c);
+    let a_clone = a.clone_without_node();
+    let center = circumcenter(a, b, c, context, Properties::default());
+    let radius = context.distance_pp(center.clone_without_node(), a_clone);
+
+    context.with_node(context.circle(center, radius), display, nodes)
+}
+
+/// Register the functions
+pub fn register(library: &mut Library) {
+    library
+        .add(
+            Function::new("circumcenter")
+                .overload(|mut col: Pc<3>, context: &CompileContext, display| {
+                    circumcenter(
+                        index!(node col, 0),
+                        index!(node col, 1),
+                        index!(node col, 2),
+                        context,
+                        display,
+                    )
+                })
+                .overload(circumcenter),
+        )
+        .add(
+            Function::new("circumcircle")
+                .overload(|mut col: Pc<3>, context: &CompileContext, display| {
+                    circumcircle(
+                        index!(node col, 0),
+                        index!(node col, 1),
+                        index!(node col, 2),
+                        context,
+                        display,
+                    )
+                })
+                .overload(circumcircle),
+        );
+}
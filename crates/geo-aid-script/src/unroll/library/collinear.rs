@@ -0,0 +1,51 @@
+//! The `collinear` function
+
+use num_traits::One;
+
+use super::prelude::*;
+use crate::take_nodes;
+use crate::token::number::ProcNum;
+use crate::unroll::Dummy;
+use crate::Error;
+
+/// `collinear(col)` - makes all points of the collection lie on one line and returns that line.
+pub fn collinear(mut col: Pc<0>, context: &mut CompileContext, display: Properties) -> Expr<Line> {
+    let nodes = take_nodes!(col);
+    let len = col.data.length;
+
+    if len < 2 {
+        context.push_error(Error::NotEnoughPoints {
+            error_span: col.span,
+            at_least: 2,
+            got: len,
+        });
+        return context.with_node(Expr::dummy(), display, nodes);
+    }
+
+    // The line is defined by the first two points, all the other ones must lie on it.
+    let line = context.line(index!(no-node col, 0), index!(no-node col, 1));
+
+    for i in 2..len {
+        context.point_on_line(&index!(no-node col, i), &line, ProcNum::one());
+    }
+
+    context.with_node(line, display, nodes)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(Function::new("collinear").overload(collinear).overload(
+        |mut a: Expr<Point>,
+         mut b: Expr<Point>,
+         mut c: Expr<Point>,
+         context: &mut CompileContext,
+         display| {
+            let nodes = take_nodes!(a, b, c);
+            let line = context.line(a, b);
+
+            context.point_on_line(&c, &line, ProcNum::one());
+
+            context.with_node(line, display, nodes)
+        },
+    ));
+}
@@ -0,0 +1,36 @@
+//! The `concyclic` function
+
+use num_traits::One;
+
+use super::prelude::*;
+use crate::take_nodes;
+use crate::token::number::ProcNum;
+
+/// `concyclic(col)` - makes all points of the collection lie on one circle and returns that circle.
+pub fn concyclic(
+    mut col: Pc<0>,
+    context: &mut CompileContext,
+    display: Properties,
+) -> Expr<Circle> {
+    let nodes = take_nodes!(col);
+
+    // The common circle is adjusted so that it goes through all the points.
+    let mut center = context.free_point();
+    let mut radius = context.free_scalar();
+
+    center.take_node();
+    radius.take_node();
+
+    let circle = context.circle(center, context.set_unit(radius, unit::DISTANCE));
+
+    for i in 0..col.data.length {
+        context.point_on_circle(&index!(no-node col, i), &circle, ProcNum::one());
+    }
+
+    context.with_node(circle, display, nodes)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(Function::new("concyclic").overload(concyclic));
+}
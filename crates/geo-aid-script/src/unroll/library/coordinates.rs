@@ -0,0 +1,18 @@
+//! The `x` and `y` functions
+
+use super::prelude::*;
+
+/// Register the functions
+pub fn register(library: &mut Library) {
+    library
+        .add(
+            Function::new("x").overload(|p: Expr<Point>, context: &CompileContext, display| {
+                Distance::from(context.point_x_display(p, display))
+            }),
+        )
+        .add(
+            Function::new("y").overload(|p: Expr<Point>, context: &CompileContext, display| {
+                Distance::from(context.point_y_display(p, display))
+            }),
+        );
+}
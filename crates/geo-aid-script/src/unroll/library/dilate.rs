@@ -0,0 +1,26 @@
+//! The `dilate` function
+
+use super::prelude::*;
+
+/// `dilate(point, point, scalar)` - the image of the first point in a homothety
+/// with the given center and ratio.
+fn dilate_function_point_point_scalar(
+    p: Expr<Point>,
+    center: Expr<Point>,
+    ratio: Unitless,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    let offset = context.mult_p(context.sub_p(p, center.clone_without_node()), ratio.0);
+
+    context.add_p_display(center, offset, display)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("dilate")
+            .alias("homothety")
+            .overload(dilate_function_point_point_scalar),
+    );
+}
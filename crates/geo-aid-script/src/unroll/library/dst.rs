@@ -13,6 +13,7 @@ pub fn distance_function_pp(
 ) -> Distance {
     let display_segment = display.get("display_segment").maybe_unset(true);
     let style = display.get("style").maybe_unset(Style::Solid);
+    let color: Option<Color> = display.get("color").get();
 
     let mut expr = context.distance_pp_display(a, b, display);
 
@@ -20,6 +21,7 @@ pub fn distance_function_pp(
         node.set_associated(Associated);
         node.insert_data("display_segment", display_segment);
         node.insert_data("style", style);
+        node.insert_data("color", color);
     }
 
     expr.into()
@@ -34,6 +36,7 @@ fn distance_function_pl(
 ) -> Distance {
     let display_segment = display.get("display_segment").maybe_unset(true);
     let style = display.get("style").maybe_unset(Style::Dashed);
+    let color: Option<Color> = display.get("color").get();
 
     let mut expr = context.distance_pl_display(a, k, display);
 
@@ -41,6 +44,7 @@ fn distance_function_pl(
         node.set_associated(Associated);
         node.insert_data("display_segment", display_segment);
         node.insert_data("style", style);
+        node.insert_data("color", color);
     }
 
     expr.into()
@@ -62,10 +66,11 @@ fn distance_convert_pc(mut pc: Pc<2>, context: &CompileContext, display: Propert
 }
 
 /// ```
-/// # use geo_aid_figure::Style;
+/// # use geo_aid_figure::{Color, Style};
 /// struct Associated {
 ///     display_segment: bool,
-///     style: Style
+///     style: Style,
+///     color: Option<Color>
 /// }
 #[derive(Debug)]
 pub struct Associated;
@@ -82,6 +87,7 @@ impl BuildAssociated<ScalarNode> for Associated {
             .as_bool()
             .unwrap();
         let style = associated.get_data("style").unwrap().as_style().unwrap();
+        let color = associated.get_data("color").unwrap().as_color().unwrap();
 
         if display_segment.unwrap() {
             match &associated.root.expr.data.data {
@@ -93,6 +99,8 @@ impl BuildAssociated<ScalarNode> for Associated {
                         q_id,
                         label: MathString::new(),
                         style: style.unwrap(),
+                        color,
+                        arrow: false,
                     });
                 }
                 ScalarData::PointLineDistance(a, k) => {
@@ -112,6 +120,8 @@ impl BuildAssociated<ScalarNode> for Associated {
                         q_id,
                         label: MathString::new(),
                         style: style.unwrap(),
+                        color,
+                        arrow: false,
                     });
                 }
                 _ => unreachable!(),
@@ -0,0 +1,18 @@
+//! The `fixed` function
+
+use super::prelude::*;
+
+/// `fixed(number, number)` - a point fixed at the given absolute coordinates.
+fn fixed_function_scalar_scalar(
+    x: Unitless,
+    y: Unitless,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    context.fixed_point_display(x.0, y.0, display)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(Function::new("fixed").overload(fixed_function_scalar_scalar));
+}
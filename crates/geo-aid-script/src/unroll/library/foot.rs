@@ -0,0 +1,30 @@
+//! The `foot` function
+
+use super::prelude::*;
+use crate::take_nodes;
+
+/// `foot(point, line)` - returns the orthogonal projection of a point onto a line.
+pub fn point_line(
+    mut point: Expr<Point>,
+    mut line: Expr<Line>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    let nodes = take_nodes!(point, line);
+
+    let foot = context.intersection(
+        line.clone_without_node(),
+        context.perpendicular_through(line, point),
+    );
+
+    context.with_node(foot, display, nodes)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(Function::new("foot").overload(point_line).overload(
+        |line: Expr<Line>, point: Expr<Point>, context: &CompileContext, props| {
+            point_line(point, line, context, props)
+        },
+    ));
+}
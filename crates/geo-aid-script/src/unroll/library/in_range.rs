@@ -0,0 +1,224 @@
+//! The `Range` type, the `range` and `in_range` functions and the `in_range` rule
+
+use num_traits::One;
+
+use crate::{
+    parser::Type,
+    take_nodes,
+    token::number::ProcNum,
+    unroll::{library::RuleOverload, AnyExpr, Convert, Node, Scalar},
+    ComplexUnit,
+};
+
+use super::{prelude::*, Overload};
+
+define_bundle! { Range {} }
+
+/// Get the common unit of a list of scalars, if there is one.
+fn common_unit(params: &[AnyExpr]) -> Option<Type> {
+    let mut unit = None;
+
+    for param in params {
+        unit = param.can_convert_to_scalar(unit)?;
+    }
+
+    Some(Type::Scalar(unit))
+}
+
+/// Make `value` lie strictly between `lo` and `hi` or, if inverted, outside that range.
+fn push_bounds(
+    value: &Expr<Scalar>,
+    lo: Expr<Scalar>,
+    hi: Expr<Scalar>,
+    context: &mut CompileContext,
+    inverted: bool,
+    weight: ProcNum,
+) {
+    if inverted {
+        // value < lo | value > hi
+        context.push_rule(UnrolledRule {
+            kind: UnrolledRuleKind::Alternative(
+                vec![
+                    UnrolledRule {
+                        kind: UnrolledRuleKind::Gt(lo, value.clone_without_node()),
+                        inverted: false,
+                        weight: ProcNum::one(),
+                    },
+                    UnrolledRule {
+                        kind: UnrolledRuleKind::Gt(value.clone_without_node(), hi),
+                        inverted: false,
+                        weight: ProcNum::one(),
+                    },
+                ]
+                .into(),
+            ),
+            inverted: false,
+            weight,
+        });
+    } else {
+        context.push_rule(UnrolledRule {
+            kind: UnrolledRuleKind::Gt(value.clone_without_node(), lo),
+            inverted: false,
+            weight: weight.clone(),
+        });
+        context.push_rule(UnrolledRule {
+            kind: UnrolledRuleKind::Gt(hi, value.clone_without_node()),
+            inverted: false,
+            weight,
+        });
+    }
+}
+
+/// `range(lo, hi)` - the range of values strictly between `lo` and `hi`.
+struct RangeFunction;
+
+impl Overload for RangeFunction {
+    fn get_returned_type(&self, params: &[AnyExpr]) -> Option<Type> {
+        // Both bounds must be scalars of the same unit.
+        if params.len() != 2 {
+            return None;
+        }
+
+        common_unit(params).map(|_| Range::get_type())
+    }
+
+    fn get_signature(&self) -> String {
+        String::from("(Scalar (any unit u), Scalar (unit u))")
+    }
+
+    fn unroll(
+        &self,
+        params: Vec<AnyExpr>,
+        context: &mut CompileContext,
+        mut props: Properties,
+    ) -> AnyExpr {
+        let ty = common_unit(&params).unwrap();
+
+        let mut params = params
+            .into_iter()
+            .map(|x| x.convert_to(ty, context).to_scalar().unwrap());
+        let mut lo = params.next().unwrap();
+        let mut hi = params.next().unwrap();
+
+        let expr = construct_bundle!(Range { lo: lo, hi: hi });
+
+        props.ignore("default-label");
+        props.finish(context);
+
+        expr.into()
+    }
+}
+
+/// `in_range(value, lo, hi)` - bounds a scalar to lie strictly between `lo` and `hi`
+/// and returns it.
+struct InRange;
+
+impl Overload for InRange {
+    fn get_returned_type(&self, params: &[AnyExpr]) -> Option<Type> {
+        // All three params must be scalars of the same unit.
+        if params.len() != 3 {
+            return None;
+        }
+
+        common_unit(params)
+    }
+
+    fn get_signature(&self) -> String {
+        String::from("(Scalar (any unit u), Scalar (unit u), Scalar (unit u))")
+    }
+
+    fn unroll(
+        &self,
+        params: Vec<AnyExpr>,
+        context: &mut CompileContext,
+        mut props: Properties,
+    ) -> AnyExpr {
+        let ty = self.get_returned_type(&params).unwrap();
+        let weight = props.get_weight(ProcNum::one());
+
+        let mut params = params
+            .into_iter()
+            .map(|x| x.convert_to(ty, context).to_scalar().unwrap());
+        let mut value = params.next().unwrap();
+        let mut lo = params.next().unwrap();
+        let mut hi = params.next().unwrap();
+        let nodes = take_nodes!(value, lo, hi);
+
+        push_bounds(&value, lo, hi, context, false, weight);
+
+        context.with_node(value, props, nodes).into()
+    }
+}
+
+/// The unit of a range's bounds, if known.
+fn range_unit(range: &AnyExpr) -> Option<Option<ComplexUnit>> {
+    match range {
+        AnyExpr::Bundle(range) => range
+            .index_without_node("lo")
+            .get_type()
+            .as_scalar()
+            .copied(),
+        _ => Some(None),
+    }
+}
+
+/// `value in_range range` - a scalar lies strictly inside the range.
+/// Inverted, it lies outside of it.
+struct InRangeRule;
+
+impl RuleOverload for InRangeRule {
+    fn matches(&self, lhs: &AnyExpr, rhs: &AnyExpr) -> bool {
+        rhs.can_convert_to_bundle("Range")
+            && range_unit(rhs).is_some_and(|unit| lhs.can_convert_to_scalar(unit).is_some())
+    }
+
+    fn get_signature(&self) -> String {
+        String::from("(Scalar (any unit u), Range (unit u))")
+    }
+
+    fn unroll(
+        &self,
+        lhs: AnyExpr,
+        rhs: AnyExpr,
+        context: &mut CompileContext,
+        props: Properties,
+        inverted: bool,
+        weight: ProcNum,
+    ) -> Box<dyn Node> {
+        let unit = range_unit(&rhs)
+            .and_then(|unit| lhs.can_convert_to_scalar(unit))
+            .flatten()
+            .or(Some(unit::SCALAR));
+        let ty = Type::Scalar(unit);
+
+        let mut value = lhs.convert_to(ty, context).to_scalar().unwrap();
+        let mut range = rhs.convert::<Bundle>(context);
+        let bound = |field, context: &CompileContext| {
+            range
+                .index_without_node(field)
+                .convert_to(ty, context)
+                .to_scalar()
+                .unwrap()
+        };
+        let lo = bound("lo", context);
+        let hi = bound("hi", context);
+
+        let mut node = CollectionNode::from_display(props, context);
+        node.extend(value.take_node());
+        node.extend(range.take_node());
+
+        push_bounds(&value, lo, hi, context, inverted, weight);
+
+        Box::new(node)
+    }
+}
+
+/// Register the type, the functions and the rule
+pub fn register(library: &mut Library) {
+    library
+        .add(Function::new("range").overload(RangeFunction))
+        .add(Function::new("in_range").overload(InRange))
+        .add(Rule::new("in_range").overload(InRangeRule));
+
+    library.bundles.insert("Range", ["lo", "hi"].into());
+}
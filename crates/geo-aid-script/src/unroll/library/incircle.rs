@@ -0,0 +1,74 @@
+//! The `incenter` and `incircle` functions
+
+use super::prelude::*;
+use crate::take_nodes;
+
+/// `incenter(point, point, point)` - the center of the circle inscribed in a triangle.
+pub fn incenter(
+    mut a: Expr<Point>,
+    mut b: Expr<Point>,
+    mut c: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    let nodes = take_nodes!(a, b, c);
+    let (a_clone, b_clone, c_clone) = (
+        a.clone_without_node(),
+        b.clone_without_node(),
+        c.clone_without_node(),
+    );
+
+    let center = context.intersection(
+        context.bisector_ppp(a, b, c),
+        context.bisector_ppp(b_clone, c_clone, a_clone),
+    );
+
+    context.with_node(center, display, nodes)
+}
+
+/// `incircle(point, point, point)` - the circle inscribed in a triangle.
+pub fn incircle(
+    mut a: Expr<Point>,
+    mut b: Expr<Point>,
+    mut c: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Circle> {
+    let nodes = take_nodes!(a, b, c);
+    let side = context.line(a.clone_without_node(), b.clone_without_node());
+    let center = incenter(a, b, c, context, Properties::default());
+    let radius = context.distance_pl(center.clone_without_node(), side);
+
+    context.with_node(context.circle(center, radius), display, nodes)
+}
+
+/// Register the functions
+pub fn register(library: &mut Library) {
+    library
+        .add(
+            Function::new("incenter")
+                .overload(|mut col: Pc<3>, context: &CompileContext, display| {
+                    incenter(
+                        index!(node col, 0),
+                        index!(node col, 1),
+                        index!(node col, 2),
+                        context,
+                        display,
+                    )
+                })
+                .overload(incenter),
+        )
+        .add(
+            Function::new("incircle")
+                .overload(|mut col: Pc<3>, context: &CompileContext, display| {
+                    incircle(
+                        index!(node col, 0),
+                        index!(node col, 1),
+                        index!(node col, 2),
+                        context,
+                        display,
+                    )
+                })
+                .overload(incircle),
+        );
+}
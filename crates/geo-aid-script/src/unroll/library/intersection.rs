@@ -1,6 +1,15 @@
 //! The `intersection` function
 
+use std::rc::Rc;
+
+use num_traits::One;
+
 use crate::math::Build;
+use crate::span;
+use crate::take_nodes;
+use crate::token::number::ProcNum;
+use crate::unroll::figure::{Node, PCNode};
+use crate::unroll::{PointCollection, PointCollectionData};
 
 use super::prelude::*;
 
@@ -21,6 +30,88 @@ fn intersection_function_ll(
     expr
 }
 
+/// `intersection(circle, circle)` - both intersection points of two circles.
+/// Which point is which is not specified.
+fn intersection_function_cc(
+    mut omega: Expr<Circle>,
+    mut gamma: Expr<Circle>,
+    context: &mut CompileContext,
+    display: Properties,
+) -> Pc<2> {
+    let nodes = take_nodes!(omega, gamma);
+
+    let points = [
+        context.free_point_on_circle(omega.clone_without_node()),
+        context.free_point_on_circle(omega),
+    ];
+
+    // Both points must also lie on the other circle.
+    for point in &points {
+        context.point_on_circle(point, &gamma, ProcNum::one());
+    }
+
+    distinct_points(points, context, display, nodes)
+}
+
+/// `intersection(line, circle)` - both intersection points of a line and a circle.
+/// Which point is which is not specified.
+fn intersection_function_lc(
+    mut k: Expr<Line>,
+    mut omega: Expr<Circle>,
+    context: &mut CompileContext,
+    display: Properties,
+) -> Pc<2> {
+    let nodes = take_nodes!(k, omega);
+
+    let points = [
+        context.free_point_on_line(k.clone_without_node()),
+        context.free_point_on_line(k),
+    ];
+
+    // Both points must also lie on the circle.
+    for point in &points {
+        context.point_on_circle(point, &omega, ProcNum::one());
+    }
+
+    distinct_points(points, context, display, nodes)
+}
+
+/// A collection of two intersection points, with a rule keeping them distinct.
+fn distinct_points(
+    mut points: [Expr<Point>; 2],
+    context: &mut CompileContext,
+    mut display: Properties,
+    nodes: Vec<Box<dyn Node>>,
+) -> Pc<2> {
+    display.ignore("default-label");
+
+    context.push_rule(UnrolledRule {
+        kind: UnrolledRuleKind::PointEq(
+            points[0].clone_without_node(),
+            points[1].clone_without_node(),
+        ),
+        inverted: true,
+        weight: ProcNum::one(),
+    });
+
+    let mut root = PCNode::new();
+    root.display = display.get("display").maybe_unset(true);
+    root.extend(points.iter_mut().map(Expr::take_node));
+    display.finish(context);
+
+    let mut node = HierarchyNode::new(root);
+    node.extend_boxed(nodes);
+
+    Pc(Expr {
+        span: span!(0, 0, 0, 0),
+        data: Rc::new(PointCollection {
+            length: 2,
+            data: PointCollectionData::PointCollection(Vec::from(points).into()),
+        }),
+        node: Some(node),
+    })
+}
+
 /// The associated data. No properties.
 #[derive(Debug)]
 pub struct Associated;
@@ -37,5 +128,10 @@ impl BuildAssociated<PointNode> for Associated {
 
 /// Register the function
 pub fn register(library: &mut Library) {
-    library.add(Function::new("intersection").overload(intersection_function_ll));
+    library.add(
+        Function::new("intersection")
+            .overload(intersection_function_ll)
+            .overload(intersection_function_cc)
+            .overload(intersection_function_lc),
+    );
 }
@@ -198,33 +198,37 @@ fn pt_lies_on_segment(
     if inverted {
         // not on the line or not between A, B
         context.push_rule(UnrolledRule {
-            kind: UnrolledRuleKind::Alternative(vec![
-                UnrolledRule {
-                    kind: UnrolledRuleKind::ScalarEq(
-                        number!(=ProcNum::zero()),
-                        context.distance_pl(point.clone_without_node(), line),
-                    ),
-                    inverted: true,
-                    weight: ProcNum::one(),
-                },
-                UnrolledRule {
-                    kind: UnrolledRuleKind::ScalarEq(
-                        context.add(
+            kind: UnrolledRuleKind::Alternative(
+                vec![
+                    UnrolledRule {
+                        kind: UnrolledRuleKind::ScalarEq(
+                            number!(=ProcNum::zero()),
+                            context.distance_pl(point.clone_without_node(), line),
+                        ),
+                        inverted: true,
+                        weight: ProcNum::one(),
+                    },
+                    UnrolledRule {
+                        kind: UnrolledRuleKind::ScalarEq(
+                            context.add(
+                                context.distance_pp(
+                                    field!(no-node POINT rhs, A with context),
+                                    point.clone_without_node(),
+                                ),
+                                context
+                                    .distance_pp(field!(no-node POINT rhs, B with context), point),
+                            ),
                             context.distance_pp(
                                 field!(no-node POINT rhs, A with context),
-                                point.clone_without_node(),
+                                field!(no-node POINT rhs, B with context),
                             ),
-                            context.distance_pp(field!(no-node POINT rhs, B with context), point),
                         ),
-                        context.distance_pp(
-                            field!(no-node POINT rhs, A with context),
-                            field!(no-node POINT rhs, B with context),
-                        ),
-                    ),
-                    inverted: true,
-                    weight: ProcNum::one(),
-                },
-            ]),
+                        inverted: true,
+                        weight: ProcNum::one(),
+                    },
+                ]
+                .into(),
+            ),
             inverted: false,
             weight,
         });
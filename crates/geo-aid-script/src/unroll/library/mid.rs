@@ -3,9 +3,10 @@ use crate::{
     unroll::{AnyExpr, Convert},
 };
 
-use super::{prelude::*, Overload};
+use super::{prelude::*, segment::Segment, Overload};
 
-struct MidPoint;
+/// Variadic overload averaging any number of points.
+pub(super) struct MidPoint;
 
 impl Overload for MidPoint {
     fn get_returned_type(&self, params: &[AnyExpr]) -> Option<Type> {
@@ -15,6 +16,10 @@ impl Overload for MidPoint {
             .then_some(Type::Point)
     }
 
+    fn get_signature(&self) -> String {
+        String::from("(Point, Point, ..., Point)")
+    }
+
     fn unroll(
         &self,
         params: Vec<AnyExpr>,
@@ -51,6 +56,10 @@ impl Overload for MidScalar {
         Some(Type::Scalar(unit))
     }
 
+    fn get_signature(&self) -> String {
+        String::from("(Scalar (any unit u), Scalar (unit u), ..., Scalar (unit u))")
+    }
+
     fn unroll(
         &self,
         params: Vec<AnyExpr>,
@@ -71,6 +80,21 @@ impl Overload for MidScalar {
     }
 }
 
+/// `mid(segment)` - the midpoint of a segment.
+fn segment_midpoint(
+    mut segment: Segment,
+    context: &CompileContext,
+    props: Properties,
+) -> Expr<Point> {
+    context.average_p_display(
+        vec![
+            field!(node POINT segment, A with context),
+            field!(node POINT segment, B with context),
+        ],
+        props,
+    )
+}
+
 // Registers the `mid` function.
 //
 // # Note:
@@ -84,6 +108,8 @@ impl Overload for MidScalar {
 pub fn register(library: &mut Library) {
     library.add(
         Function::new("mid")
+            .alias("midpoint")
+            .overload(segment_midpoint)
             .overload(|mut col: Pc<0>, context: &CompileContext, props| {
                 context.average_p_display(
                     (0..col.0.data.length)
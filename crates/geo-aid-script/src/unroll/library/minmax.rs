@@ -0,0 +1,98 @@
+//! The `min` and `max` functions
+
+use num_traits::FromPrimitive;
+
+use crate::{
+    parser::Type,
+    token::number::ProcNum,
+    unroll::{AnyExpr, Scalar},
+};
+
+use super::{prelude::*, Overload};
+
+/// Variadic overload taking the smallest or the largest of at least two scalars of the same unit.
+struct Extremum {
+    /// Whether this is `max` (as opposed to `min`).
+    max: bool,
+}
+
+impl Extremum {
+    /// Take the extremum of two scalars. Expressed through
+    /// `max(a, b) = (a + b + |a - b|) / 2` and `min(a, b) = (a + b - |a - b|) / 2`.
+    fn pair(
+        &self,
+        a: Expr<Scalar>,
+        b: Expr<Scalar>,
+        context: &CompileContext,
+        display: Properties,
+    ) -> Expr<Scalar> {
+        let diff = context.abs(context.sub(a.clone_without_node(), b.clone_without_node()));
+        let sum = context.add(a, b);
+        let doubled = if self.max {
+            context.add(sum, diff)
+        } else {
+            context.sub(sum, diff)
+        };
+
+        context.div_display(
+            doubled,
+            number!(SCALAR ProcNum::from_i32(2).unwrap()),
+            display,
+        )
+    }
+}
+
+impl Overload for Extremum {
+    fn get_returned_type(&self, params: &[AnyExpr]) -> Option<Type> {
+        // Same as with `mid`: all params must be scalars of the same unit.
+        if params.len() < 2 {
+            return None;
+        }
+
+        let mut unit = None;
+
+        for param in params {
+            if let Some(u) = param.can_convert_to_scalar(unit) {
+                unit = u;
+            } else {
+                return None;
+            }
+        }
+
+        Some(Type::Scalar(unit))
+    }
+
+    fn get_signature(&self) -> String {
+        String::from("(Scalar (any unit u), Scalar (unit u), ..., Scalar (unit u))")
+    }
+
+    fn unroll(
+        &self,
+        params: Vec<AnyExpr>,
+        context: &mut CompileContext,
+        props: Properties,
+    ) -> AnyExpr {
+        let ty = self.get_returned_type(&params).unwrap();
+
+        let mut params = params
+            .into_iter()
+            .map(|x| x.convert_to(ty, context).to_scalar().unwrap());
+
+        let mut result = params.next().unwrap();
+        let mut next = params.next().unwrap();
+
+        for param in params {
+            result = self.pair(result, next, context, Properties::default());
+            next = param;
+        }
+
+        self.pair(result, next, context, props).into()
+    }
+}
+
+/// Register the functions
+pub fn register(library: &mut Library) {
+    library
+        .add(Function::new("min").overload(Extremum { max: false }))
+        .add(Function::new("max").overload(Extremum { max: true }));
+}
@@ -0,0 +1,46 @@
+//! The `nine_point_circle` function
+
+use super::prelude::*;
+use crate::take_nodes;
+
+/// `nine_point_circle(point, point, point)` - the nine-point circle of a triangle,
+/// constructed as the circumcircle of its sides' midpoints.
+pub fn nine_point_circle(
+    mut a: Expr<Point>,
+    mut b: Expr<Point>,
+    mut c: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Circle> {
+    let nodes = take_nodes!(a, b, c);
+    let mid = |p: &Expr<Point>, q: &Expr<Point>| {
+        context.average_p(vec![p.clone_without_node(), q.clone_without_node()])
+    };
+
+    let circle = super::circumcircle::circumcircle(
+        mid(&a, &b),
+        mid(&b, &c),
+        mid(&c, &a),
+        context,
+        Properties::default(),
+    );
+
+    context.with_node(circle, display, nodes)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("nine_point_circle")
+            .overload(|mut col: Pc<3>, context: &CompileContext, display| {
+                nine_point_circle(
+                    index!(node col, 0),
+                    index!(node col, 1),
+                    index!(node col, 2),
+                    context,
+                    display,
+                )
+            })
+            .overload(nine_point_circle),
+    );
+}
@@ -0,0 +1,12 @@
+//! The `number` function.
+
+use super::prelude::*;
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("number").overload(|context: &CompileContext, props| {
+            Unitless::from(context.free_scalar_display(props))
+        }),
+    );
+}
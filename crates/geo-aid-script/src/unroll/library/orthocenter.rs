@@ -0,0 +1,40 @@
+//! The `orthocenter` function
+
+use super::prelude::*;
+use crate::take_nodes;
+
+/// `orthocenter(point, point, point)` - the intersection of a triangle's altitudes.
+pub fn orthocenter(
+    mut a: Expr<Point>,
+    mut b: Expr<Point>,
+    mut c: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    let nodes = take_nodes!(a, b, c);
+    let (a_clone, c_clone) = (a.clone_without_node(), c.clone_without_node());
+
+    let center = context.intersection(
+        context.perpendicular_through(context.line(b.clone_without_node(), c), a),
+        context.perpendicular_through(context.line(a_clone, c_clone), b),
+    );
+
+    context.with_node(center, display, nodes)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("orthocenter")
+            .overload(|mut col: Pc<3>, context: &CompileContext, display| {
+                orthocenter(
+                    index!(node col, 0),
+                    index!(node col, 1),
+                    index!(node col, 2),
+                    context,
+                    display,
+                )
+            })
+            .overload(orthocenter),
+    );
+}
@@ -1,6 +1,9 @@
-//! The `parallel_through` function
+//! The `parallel_through` function and the `parallel` rule
+
+use num_traits::Zero;
 
 use super::prelude::*;
+use crate::token::number::ProcNum;
 
 /// `parallel_through(line, point)` - returns a line parallel to the 1st argument going through point at 2nd argument.
 fn line_point(
@@ -12,8 +15,36 @@ fn line_point(
     context.parallel_through_display(line, point, display)
 }
 
-/// Register the function
+/// `line parallel line` - two lines are parallel.
+fn line_parallel_line(
+    mut lhs: Expr<Line>,
+    mut rhs: Expr<Line>,
+    context: &mut CompileContext,
+    display: Properties,
+    inverted: bool,
+    weight: ProcNum,
+) -> CollectionNode {
+    let mut node = CollectionNode::from_display(display, context);
+    node.extend(lhs.node.take());
+    node.extend(rhs.node.take());
+
+    // The angle between lines can be either 0 or 180 degrees for parallel lines, hence the sine.
+    context.push_rule(UnrolledRule {
+        kind: UnrolledRuleKind::ScalarEq(
+            context.sin(context.angle_ll(lhs, rhs)),
+            number!(=ProcNum::zero()),
+        ),
+        inverted,
+        weight,
+    });
+
+    node
+}
+
+/// Register the function and the rule
 pub fn register(library: &mut Library) {
+    library.add(Rule::new("parallel").overload(line_parallel_line));
+
     library.add(
         Function::new("parallel_through")
             .alias("parallel")
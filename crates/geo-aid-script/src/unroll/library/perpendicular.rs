@@ -1,6 +1,9 @@
-//! The `perpendicular_through` function
+//! The `perpendicular_through` function and the `perpendicular` rule
+
+use num_traits::FromPrimitive;
 
 use super::prelude::*;
+use crate::token::number::ProcNum;
 
 /// `perpendicular_through(line, point)` - returns a line perpendicular to the 1st argument going through point at 2nd argument.
 pub fn line_point(
@@ -12,8 +15,35 @@ pub fn line_point(
     context.perpendicular_through_display(line, point, display)
 }
 
-/// Register the function
+/// `line perpendicular line` - two lines are perpendicular.
+fn line_perpendicular_line(
+    mut lhs: Expr<Line>,
+    mut rhs: Expr<Line>,
+    context: &mut CompileContext,
+    display: Properties,
+    inverted: bool,
+    weight: ProcNum,
+) -> CollectionNode {
+    let mut node = CollectionNode::from_display(display, context);
+    node.extend(lhs.node.take());
+    node.extend(rhs.node.take());
+
+    context.push_rule(UnrolledRule {
+        kind: UnrolledRuleKind::ScalarEq(
+            context.angle_ll(lhs, rhs),
+            number!(ANGLE ProcNum::pi() / &ProcNum::from_i32(2).unwrap()),
+        ),
+        inverted,
+        weight,
+    });
+
+    node
+}
+
+/// Register the function and the rule
 pub fn register(library: &mut Library) {
+    library.add(Rule::new("perpendicular").overload(line_perpendicular_line));
+
     library.add(
         Function::new("perpendicular_through")
             .alias("pependicular")
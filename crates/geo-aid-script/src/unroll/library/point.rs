@@ -5,8 +5,15 @@ use super::prelude::*;
 /// Register the function
 pub fn register(library: &mut Library) {
     library.add(
-        Function::new("point").overload(|context: &CompileContext, props| {
-            context.expr_with(Point::Free, props, Vec::new())
-        }),
+        Function::new("point")
+            .overload(|context: &CompileContext, props| {
+                context.expr_with(Point::Free, props, Vec::new())
+            })
+            .overload(|line: Expr<Line>, context: &CompileContext, props| {
+                context.free_point_on_line_display(line, props)
+            })
+            .overload(|circle: Expr<Circle>, context: &CompileContext, props| {
+                context.free_point_on_circle_display(circle, props)
+            }),
     );
 }
@@ -0,0 +1,96 @@
+//! The `Polygon` type and function
+
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use crate::{
+    figure::SpannedMathString,
+    span, take_nodes,
+    unroll::{figure::MaybeUnset, AnyExpr, BundleData, Generic},
+    Error,
+};
+
+use super::{
+    prelude::*,
+    segment::{self, Segment},
+};
+
+define_bundle! { Polygon {} }
+
+/// `polygon(point collection)` - the given points, connected with a closed chain of segments.
+fn polygon_function_pc(
+    mut col: Pc<0>,
+    context: &CompileContext,
+    mut display: Properties,
+) -> Polygon {
+    display.ignore("default-label");
+    let shown = display.get("display").maybe_unset(true);
+    let style = display.get("style").maybe_unset(Style::default());
+    let color: Option<Color> = display.get("color").get();
+    display.finish(context);
+
+    let nodes = take_nodes!(col);
+    let length = col.data.length;
+
+    // One or two points would make the edges degenerate.
+    if length < 3 {
+        context.push_error(Error::NotEnoughPoints {
+            error_span: col.span,
+            at_least: 3,
+            got: length,
+        });
+
+        return Polygon::from(Expr {
+            span: col.span,
+            data: Rc::new(Bundle {
+                name: "Polygon",
+                data: BundleData::Generic(Generic::Dummy),
+            }),
+            node: None,
+        });
+    }
+
+    let mut fields = HashMap::new();
+    let mut edges = Vec::new();
+
+    for i in 0..length {
+        fields.insert(i.to_string(), AnyExpr::from(index!(no-node col, i)));
+
+        let mut a = index!(no-node col, i);
+        let mut b = index!(no-node col, (i + 1) % length);
+        let mut edge = construct_bundle!(Segment { A: a, B: b });
+
+        if let Some(mut node) = edge.node.take() {
+            node.insert_data("display_segment", MaybeUnset::new(true));
+            node.insert_data("style", style);
+            node.insert_data("color", color);
+            node.insert_data("arrow", MaybeUnset::new(false));
+            node.insert_data("label", SpannedMathString::new(span!(0, 0, 0, 0)));
+            node.set_associated(segment::Associated);
+            edges.push(node);
+        }
+    }
+
+    let mut root = BundleNode::new();
+    root.display = shown;
+    let mut node = HierarchyNode::new(root);
+    node.extend_children(edges);
+    node.extend_boxed(nodes);
+
+    Polygon::from(Expr {
+        span: col.span,
+        data: Rc::new(Bundle {
+            name: "Polygon",
+            data: BundleData::ConstructBundle(fields.into()),
+        }),
+        node: Some(node),
+    })
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(Function::new("polygon").overload(polygon_function_pc));
+    library.bundles.insert("Polygon", HashSet::new());
+}
@@ -0,0 +1,142 @@
+//! The `sqrt` and `pow` functions
+
+use crate::{
+    parser::Type,
+    token::{number::CompExponent, Span},
+    unroll::{AnyExpr, Convert, Node, Scalar},
+    ComplexUnit, Error,
+};
+
+use super::{prelude::*, Overload};
+
+/// Get the value of an exponent given as a number literal, if it is one.
+fn literal_exponent(expr: &Expr<Scalar>) -> Option<CompExponent> {
+    match &expr.data.data {
+        ScalarData::Number(v) => v.to_exponent(),
+        ScalarData::Negate(v) => literal_exponent(v).map(|x| -x),
+        _ => None,
+    }
+}
+
+/// Check if the unit has a square root, i.e. all its exponents are even.
+fn has_square_root(unit: &ComplexUnit) -> bool {
+    unit.iter()
+        .all(|v| v.is_integer() && v.to_integer() % 2 == 0)
+}
+
+/// Raise a scalar of any unit to a constant power.
+fn power(
+    base: AnyExpr,
+    exponent: CompExponent,
+    context: &CompileContext,
+    display: Properties,
+) -> AnyExpr {
+    let mut base: Expr<Scalar> = base.convert(context);
+    let node = base.take_node();
+
+    context
+        .expr_with(
+            Scalar {
+                unit: base.data.unit.map(|v| v.pow(exponent)),
+                data: ScalarData::Pow(base, exponent),
+            },
+            display,
+            node.into_iter()
+                .map(|x| Box::new(x) as Box<dyn Node>)
+                .collect(),
+        )
+        .into()
+}
+
+/// `sqrt(value)` - square root of a scalar of any unit.
+struct Sqrt;
+
+impl Overload for Sqrt {
+    fn get_returned_type(&self, params: &[AnyExpr]) -> Option<Type> {
+        if params.len() != 1 {
+            return None;
+        }
+
+        let unit = params[0].can_convert_to_scalar(None)?;
+
+        if unit.is_some_and(|unit| !has_square_root(&unit)) {
+            return None;
+        }
+
+        Some(Type::Scalar(unit.map(|v| v.pow(CompExponent::new(1, 2)))))
+    }
+
+    fn get_signature(&self) -> String {
+        String::from("(Scalar (any unit with even exponents))")
+    }
+
+    fn get_mismatch_error(&self, params: &[AnyExpr], error_span: Span) -> Option<Error> {
+        if params.len() != 1 {
+            return None;
+        }
+
+        let unit = params[0].can_convert_to_scalar(None)??;
+
+        (!has_square_root(&unit)).then_some(Error::OddUnitExponent { error_span, unit })
+    }
+
+    fn unroll(
+        &self,
+        mut params: Vec<AnyExpr>,
+        context: &mut CompileContext,
+        props: Properties,
+    ) -> AnyExpr {
+        power(
+            params.swap_remove(0),
+            CompExponent::new(1, 2),
+            context,
+            props,
+        )
+    }
+}
+
+/// `pow(value, exponent)` - a scalar of any unit raised to a power given as a number literal.
+struct Pow;
+
+impl Overload for Pow {
+    fn get_returned_type(&self, params: &[AnyExpr]) -> Option<Type> {
+        if params.len() != 2 {
+            return None;
+        }
+
+        let unit = params[0].can_convert_to_scalar(None)?;
+        params[1].can_convert_to_scalar(Some(unit::SCALAR))?;
+
+        // The resulting unit depends on the exponent, so it must be known upfront.
+        let exponent = match &params[1] {
+            AnyExpr::Scalar(v) => literal_exponent(v)?,
+            _ => return None,
+        };
+
+        Some(Type::Scalar(unit.map(|v| v.pow(exponent))))
+    }
+
+    fn get_signature(&self) -> String {
+        String::from("(Scalar (any unit), Scalar (no unit))")
+    }
+
+    fn unroll(
+        &self,
+        mut params: Vec<AnyExpr>,
+        context: &mut CompileContext,
+        props: Properties,
+    ) -> AnyExpr {
+        let exponent: Expr<Scalar> = params.pop().unwrap().convert(context);
+
+        let exponent = literal_exponent(&exponent).unwrap();
+
+        power(params.pop().unwrap(), exponent, context, props)
+    }
+}
+
+/// Register the functions
+pub fn register(library: &mut Library) {
+    library
+        .add(Function::new("sqrt").overload(Sqrt))
+        .add(Function::new("pow").overload(Pow));
+}
@@ -0,0 +1,17 @@
+//! The `radius` function
+
+use super::prelude::*;
+
+/// `radius(circle)` - the radius of a circle.
+fn radius_function_circle(
+    circle: Expr<Circle>,
+    context: &CompileContext,
+    display: Properties,
+) -> Distance {
+    context.circle_radius_display(circle, display).into()
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(Function::new("radius").overload(radius_function_circle));
+}
@@ -0,0 +1,102 @@
+//! The `Ray` type and function
+
+use crate::{
+    figure::{RayItem, SpannedMathString},
+    math::Build,
+    span,
+};
+
+use super::prelude::*;
+
+define_bundle! { Ray {} }
+
+/// `Ray(point, point)` - a ray starting at the first point and going through the second one.
+fn ray_function_point_point(
+    mut a: Expr<Point>,
+    mut b: Expr<Point>,
+    context: &CompileContext,
+    mut display: Properties,
+) -> Ray {
+    let mut expr = construct_bundle!(Ray { A: a, B: b });
+
+    if let Some(node) = &mut expr.node {
+        display.ignore("default-label");
+        node.root.display = display.get("display").maybe_unset(true);
+
+        let display_ray = display.get("display_ray").maybe_unset(true);
+        let style = display.get("style").maybe_unset(Style::default());
+        let color: Option<Color> = display.get("color").get();
+        let arrow = display.get("arrow").maybe_unset(false);
+        let label = display
+            .get("label")
+            .ok_or(SpannedMathString::new(span!(0, 0, 0, 0)));
+
+        node.insert_data("display_ray", display_ray);
+        node.insert_data("style", style);
+        node.insert_data("color", color);
+        node.insert_data("arrow", arrow);
+        node.insert_data("label", label);
+        node.set_associated(Associated);
+    }
+
+    display.finish(context);
+
+    expr
+}
+
+/// ```
+/// # use geo_aid_figure::{math_string::MathString, Color, Style};
+/// struct Associated {
+///     display_ray: bool,
+///     style: Style,
+///     color: Option<Color>,
+///     arrow: bool,
+///     label: MathString
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Associated;
+
+impl BuildAssociated<BundleNode> for Associated {
+    fn build_associated(
+        self: Box<Self>,
+        build: &mut Build,
+        associated: &mut HierarchyNode<BundleNode>,
+    ) {
+        let display_ray = associated
+            .get_data("display_ray")
+            .unwrap()
+            .as_bool()
+            .unwrap();
+        let style = associated.get_data("style").unwrap().as_style().unwrap();
+        let color = associated.get_data("color").unwrap().as_color().unwrap();
+        let arrow = associated.get_data("arrow").unwrap().as_bool().unwrap();
+        let label = associated.get_data("label").unwrap().as_label().unwrap();
+
+        if display_ray.unwrap() {
+            let p_id = build.load(associated.root.children["A"].as_point().unwrap());
+            let q_id = build.load(associated.root.children["B"].as_point().unwrap());
+            build.add(RayItem {
+                p_id,
+                q_id,
+                label: label.string.clone(),
+                style: style.unwrap(),
+                color,
+                arrow: arrow.unwrap(),
+            });
+        }
+    }
+}
+
+/// Register the type and the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("ray")
+            .overload(|mut col: Pc<2>, context: &CompileContext, display| {
+                ray_function_point_point(index!(node col,0), index!(node col,1), context, display)
+            })
+            .overload(ray_function_point_point),
+    );
+
+    library.bundles.insert("Ray", ["A", "B"].into());
+}
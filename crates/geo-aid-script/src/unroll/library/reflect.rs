@@ -0,0 +1,60 @@
+//! The `reflect` function
+
+use num_traits::FromPrimitive;
+
+use super::prelude::*;
+use crate::take_nodes;
+use crate::token::number::ProcNum;
+
+/// Reflection of a point across another point, with no display.
+fn central(point: Expr<Point>, center: Expr<Point>, context: &CompileContext) -> Expr<Point> {
+    context.sub_p(
+        context.mult_p(center, number!(SCALAR ProcNum::from_i32(2).unwrap())),
+        point,
+    )
+}
+
+/// `reflect(point, line)` - returns the reflection of a point across a line.
+pub fn point_line(
+    mut point: Expr<Point>,
+    mut line: Expr<Line>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    let nodes = take_nodes!(point, line);
+
+    // The foot of the perpendicular is the midpoint of the point and its reflection.
+    let foot = context.intersection(
+        context.perpendicular_through(line.clone_without_node(), point.clone_without_node()),
+        line,
+    );
+
+    context.with_node(central(point, foot, context), display, nodes)
+}
+
+/// `reflect(point, point)` - returns the reflection of a point across another point (central symmetry).
+pub fn point_point(
+    mut point: Expr<Point>,
+    mut center: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    let nodes = take_nodes!(point, center);
+
+    context.with_node(central(point, center, context), display, nodes)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("reflect")
+            .alias("reflection")
+            .overload(point_line)
+            .overload(
+                |line: Expr<Line>, point: Expr<Point>, context: &CompileContext, props| {
+                    point_line(point, line, context, props)
+                },
+            )
+            .overload(point_point),
+    );
+}
@@ -0,0 +1,26 @@
+//! The `rotate` function
+
+use super::prelude::*;
+
+/// `rotate(point, point, angle)` - the image of the first point in a rotation
+/// about the given center by the given angle (counterclockwise).
+fn rotate_function_point_point_angle(
+    p: Expr<Point>,
+    center: Expr<Point>,
+    angle: Angle,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    let offset = context.rotate_p(context.sub_p(p, center.clone_without_node()), angle.0);
+
+    context.add_p_display(center, offset, display)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("rotate")
+            .alias("rotation")
+            .overload(rotate_function_point_point_angle),
+    );
+}
@@ -1,10 +1,15 @@
 //! The `Segment` type and function
 
-use crate::{figure::SegmentItem, math::Build, parser::PropertyValue, span, token::StrLit};
+use crate::{
+    figure::{SegmentItem, SpannedMathString},
+    math::Build,
+    parser::PropertyValue,
+    span,
+    token::StrLit,
+};
 
 use super::prelude::*;
 use crate::token::Span;
-use geo_aid_figure::math_string::MathString;
 
 define_bundle! { Segment {} }
 
@@ -23,9 +28,17 @@ fn segment_function_point_point(
 
         let display_segment = display.get("display_segment").maybe_unset(true);
         let style = display.get("style").maybe_unset(Style::default());
+        let color: Option<Color> = display.get("color").get();
+        let arrow = display.get("arrow").maybe_unset(false);
+        let label = display
+            .get("label")
+            .ok_or(SpannedMathString::new(span!(0, 0, 0, 0)));
 
         node.insert_data("display_segment", display_segment);
         node.insert_data("style", style);
+        node.insert_data("color", color);
+        node.insert_data("arrow", arrow);
+        node.insert_data("label", label);
         node.set_associated(Associated);
     }
 
@@ -35,10 +48,13 @@ fn segment_function_point_point(
 }
 
 /// ```
-/// # use geo_aid_figure::Style;
+/// # use geo_aid_figure::{math_string::MathString, Color, Style};
 /// struct Associated {
 ///     display_segment: bool,
-///     style: Style
+///     style: Style,
+///     color: Option<Color>,
+///     arrow: bool,
+///     label: MathString
 /// }
 /// ```
 #[derive(Debug)]
@@ -56,6 +72,9 @@ impl BuildAssociated<BundleNode> for Associated {
             .as_bool()
             .unwrap();
         let style = associated.get_data("style").unwrap().as_style().unwrap();
+        let color = associated.get_data("color").unwrap().as_color().unwrap();
+        let arrow = associated.get_data("arrow").unwrap().as_bool().unwrap();
+        let label = associated.get_data("label").unwrap().as_label().unwrap();
 
         if display_segment.unwrap() {
             let p_id = build.load(associated.root.children["A"].as_point().unwrap());
@@ -63,8 +82,10 @@ impl BuildAssociated<BundleNode> for Associated {
             build.add(SegmentItem {
                 p_id,
                 q_id,
-                label: MathString::new(),
+                label: label.string.clone(),
                 style: style.unwrap(),
+                color,
+                arrow: arrow.unwrap(),
             });
         }
     }
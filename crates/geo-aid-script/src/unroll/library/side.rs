@@ -0,0 +1,87 @@
+//! The `same_side` and `opposite_side` rules
+
+use num_traits::FromPrimitive;
+
+use super::prelude::*;
+use crate::{token::number::ProcNum, unroll::Scalar};
+
+/// Distances of `P`, `Q` and the midpoint `M` of `PQ` from the line, in that order.
+/// `M` is exactly as far as the mean of `P` and `Q` if they are on the same side,
+/// or as half their difference if they are on opposite sides.
+fn distances(
+    col: Pc<2>,
+    line: &Expr<Line>,
+    context: &CompileContext,
+) -> (Expr<Scalar>, Expr<Scalar>, Expr<Scalar>) {
+    let p = index!(no-node col, 0);
+    let q = index!(no-node col, 1);
+
+    (
+        context.distance_pl(p.clone_without_node(), line.clone_without_node()),
+        context.distance_pl(q.clone_without_node(), line.clone_without_node()),
+        context.distance_pl(context.average_p(vec![p, q]), line.clone_without_node()),
+    )
+}
+
+/// `PQ same_side line` - points `P` and `Q` lie on the same side of the line.
+fn pc_same_side_line(
+    mut lhs: Pc<2>,
+    mut rhs: Expr<Line>,
+    context: &mut CompileContext,
+    display: Properties,
+    inverted: bool,
+    weight: ProcNum,
+) -> CollectionNode {
+    let mut node = CollectionNode::from_display(display, context);
+    node.extend(lhs.node.take());
+    node.extend(rhs.node.take());
+
+    let (p, q, m) = distances(lhs, &rhs, context);
+
+    // 2 * d(M) = d(P) + d(Q)
+    context.push_rule(UnrolledRule {
+        kind: UnrolledRuleKind::ScalarEq(
+            context.mult(number!(SCALAR ProcNum::from_i32(2).unwrap()), m),
+            context.add(p, q),
+        ),
+        inverted,
+        weight,
+    });
+
+    node
+}
+
+/// `PQ opposite_side line` - points `P` and `Q` lie on opposite sides of the line.
+fn pc_opposite_side_line(
+    mut lhs: Pc<2>,
+    mut rhs: Expr<Line>,
+    context: &mut CompileContext,
+    display: Properties,
+    inverted: bool,
+    weight: ProcNum,
+) -> CollectionNode {
+    let mut node = CollectionNode::from_display(display, context);
+    node.extend(lhs.node.take());
+    node.extend(rhs.node.take());
+
+    let (p, q, m) = distances(lhs, &rhs, context);
+
+    // 2 * d(M) = |d(P) - d(Q)|
+    context.push_rule(UnrolledRule {
+        kind: UnrolledRuleKind::ScalarEq(
+            context.mult(number!(SCALAR ProcNum::from_i32(2).unwrap()), m),
+            context.abs(context.sub(p, q)),
+        ),
+        inverted,
+        weight,
+    });
+
+    node
+}
+
+/// Register the rules
+pub fn register(library: &mut Library) {
+    library
+        .add(Rule::new("same_side").overload(pc_same_side_line))
+        .add(Rule::new("opposite_side").overload(pc_opposite_side_line));
+}
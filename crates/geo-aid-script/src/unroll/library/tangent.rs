@@ -0,0 +1,86 @@
+//! The `tangent` and `tangent_at` functions
+
+use num_traits::{FromPrimitive, One};
+
+use super::prelude::*;
+use crate::token::number::ProcNum;
+
+/// `tangent(point, circle)` - a tangent line to a circle going through a point.
+/// There are two such lines, which one is picked is decided by the optimizer.
+pub fn point_circle(
+    point: Expr<Point>,
+    mut circle: Expr<Circle>,
+    context: &mut CompileContext,
+    display: Properties,
+) -> Expr<Line> {
+    let circle_node = circle.take_node();
+
+    // The tangency point is adjusted to lie on the circle...
+    let mut tangency = context.free_point();
+    tangency.take_node();
+    context.point_on_circle(&tangency, &circle, ProcNum::one());
+
+    // ...so that the radius going to it is perpendicular to the tangent.
+    context.push_rule(UnrolledRule {
+        kind: UnrolledRuleKind::ScalarEq(
+            context.angle_ppp(
+                context.circle_center(circle),
+                tangency.clone_without_node(),
+                point.clone_without_node(),
+            ),
+            number!(ANGLE ProcNum::pi() / &ProcNum::from_i32(2).unwrap()),
+        ),
+        inverted: false,
+        weight: ProcNum::one(),
+    });
+
+    let mut line = context.line_display(point, tangency, display);
+
+    if let Some(node) = &mut line.node {
+        node.extend_children(circle_node);
+    }
+
+    line
+}
+
+/// `tangent_at(circle, point)` - the tangent line to a circle at a point lying on it.
+/// Unlike [`point_circle`], this is unambiguous: it's the line through the point perpendicular to the radius.
+pub fn circle_point_at(
+    mut circle: Expr<Circle>,
+    point: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Line> {
+    let circle_node = circle.take_node();
+
+    let radius = context.line(context.circle_center(circle), point.clone_without_node());
+    let mut line = context.perpendicular_through_display(radius, point, display);
+
+    if let Some(node) = &mut line.node {
+        node.extend_children(circle_node);
+    }
+
+    line
+}
+
+/// Register the functions
+pub fn register(library: &mut Library) {
+    library
+        .add(Function::new("tangent").overload(point_circle).overload(
+            |circle: Expr<Circle>, point: Expr<Point>, context: &mut CompileContext, display| {
+                point_circle(point, circle, context, display)
+            },
+        ))
+        .add(
+            Function::new("tangent_at")
+                .overload(circle_point_at)
+                .overload(
+                    |point: Expr<Point>,
+                     circle: Expr<Circle>,
+                     context: &CompileContext,
+                     display| {
+                        circle_point_at(circle, point, context, display)
+                    },
+                ),
+        );
+}
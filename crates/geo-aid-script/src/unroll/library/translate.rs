@@ -0,0 +1,23 @@
+//! The `translate` function
+
+use super::prelude::*;
+
+/// `translate(point, point, point)` - translate the first point by the vector going from the second point to the third one.
+fn translate_function_point_point_point(
+    p: Expr<Point>,
+    from: Expr<Point>,
+    to: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    context.add_p_display(p, context.sub_p(to, from), display)
+}
+
+/// Register the function
+pub fn register(library: &mut Library) {
+    library.add(
+        Function::new("translate")
+            .alias("translation")
+            .overload(translate_function_point_point_point),
+    );
+}
@@ -0,0 +1,23 @@
+//! The `sin`, `cos` and `tan` functions
+
+use super::prelude::*;
+
+/// Register the functions
+pub fn register(library: &mut Library) {
+    library
+        .add(
+            Function::new("sin").overload(|v: Angle, context: &CompileContext, display| {
+                Unitless::from(context.sin_display(v.0, display))
+            }),
+        )
+        .add(
+            Function::new("cos").overload(|v: Angle, context: &CompileContext, display| {
+                Unitless::from(context.cos_display(v.0, display))
+            }),
+        )
+        .add(
+            Function::new("tan").overload(|v: Angle, context: &CompileContext, display| {
+                Unitless::from(context.tan_display(v.0, display))
+            }),
+        );
+}
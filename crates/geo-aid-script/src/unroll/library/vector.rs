@@ -0,0 +1,103 @@
+//! The `Vector` type and the `vec` function
+
+use super::prelude::*;
+use crate::{
+    parser::BinaryOperator,
+    unroll::{AnyExpr, Bundle, Convert},
+};
+
+define_bundle! { Vector {} }
+
+/// `vec(point, point)` - a vector going from the first point to the second one.
+fn vector_function_point_point(
+    mut a: Expr<Point>,
+    mut b: Expr<Point>,
+    context: &CompileContext,
+    mut display: Properties,
+) -> Vector {
+    let expr = construct_bundle!(Vector { A: a, B: b });
+
+    display.ignore("default-label");
+    display.finish(context);
+
+    expr
+}
+
+/// Check if the expression is a vector.
+#[must_use]
+pub fn is_vector(expr: &AnyExpr) -> bool {
+    expr.get_type() == Vector::get_type()
+}
+
+/// Get the vector's displacement, as a point relative to the origin.
+fn displacement(vector: &Expr<Bundle>, context: &mut CompileContext) -> Expr<Point> {
+    let end = field!(no-node POINT vector, B with context);
+    let start = field!(no-node POINT vector, A with context);
+
+    context.sub_p(end, start)
+}
+
+/// Move a point by a displacement, forwards or backwards depending on the operator.
+fn translate(
+    operator: &BinaryOperator,
+    point: Expr<Point>,
+    displacement: Expr<Point>,
+    context: &CompileContext,
+    display: Properties,
+) -> Expr<Point> {
+    match operator {
+        BinaryOperator::Add(_) => context.add_p_display(point, displacement, display),
+        BinaryOperator::Sub(_) => context.sub_p_display(point, displacement, display),
+        BinaryOperator::Mul(_) | BinaryOperator::Div(_) => unreachable!(),
+    }
+}
+
+/// Unroll an addition or a subtraction of a vector (`rhs`).
+/// `point ± vector` is a point, while `vector ± vector` is a vector with the same start as `lhs`.
+pub fn unroll_binop(
+    operator: &BinaryOperator,
+    lhs: AnyExpr,
+    rhs: AnyExpr,
+    context: &mut CompileContext,
+    mut display: Properties,
+) -> AnyExpr {
+    let AnyExpr::Bundle(mut rhs) = rhs else {
+        unreachable!()
+    };
+    let rhs_node = rhs.take_node();
+    let displacement = displacement(&rhs, context);
+
+    if let AnyExpr::Bundle(mut lhs) = lhs {
+        let lhs_node = lhs.take_node();
+        let mut start = field!(no-node POINT lhs, A with context);
+        let end = field!(no-node POINT lhs, B with context);
+        let mut end = translate(operator, end, displacement, context, Properties::default());
+
+        let mut expr = construct_bundle!(Vector { A: start, B: end });
+        if let Some(node) = &mut expr.node {
+            node.extend_children(lhs_node);
+            node.extend_children(rhs_node);
+        }
+
+        display.ignore("default-label");
+        display.finish(context);
+
+        expr.into()
+    } else {
+        let point = lhs.convert::<Point>(context);
+        let mut expr = translate(operator, point, displacement, context, display);
+
+        if let Some(node) = &mut expr.node {
+            node.extend_children(rhs_node);
+        }
+
+        expr.into()
+    }
+}
+
+/// Register the type and the function
+pub fn register(library: &mut Library) {
+    library.add(Function::new("vec").overload(vector_function_point_point));
+
+    library.bundles.insert("Vector", ["A", "B"].into());
+}
@@ -1,15 +1,31 @@
 //! Geo-AID is capable of outputting figures as a simple svg file. This file may not be possible
 //! to display everywhere, but it should be suitable for most cases.
 
+use std::f64::consts::PI;
+
+use geo_aid_figure::math_string::{MathChar, MathIndex, MathSpecial, MathString, GREEK};
 use geo_aid_figure::{
-    CircleItem, Figure, Item, LineItem, PointItem, Position, Style, TwoPointItem,
+    AngleItem, CircleItem, Color, Figure, Item, Label, LineItem, PointItem, Position, Style,
+    TwoPointItem, ANGLE_ARC_RADIUS, ANGLE_ARC_SPACING, RIGHT_ANGLE_MARK_SIZE,
 };
 
+/// The largest fraction of the shorter arm the outermost arc may reach.
+const ANGLE_ARC_ARM_FRACTION: f64 = 0.4;
+
+/// Label font size as a fraction of the smaller canvas dimension.
+const LABEL_FONT_SCALE: f64 = 0.025;
+
 /// The SVG format writer.
 #[derive(Debug, Default)]
 pub struct Svg {
     /// Current file contents
     content: String,
+    /// Canvas width
+    width: f64,
+    /// Canvas height
+    height: f64,
+    /// Font size of labels
+    font_size: f64,
 }
 
 impl Svg {
@@ -27,6 +43,7 @@ impl Svg {
                 Item::Ray(ray) => svg.draw_ray(ray),
                 Item::Segment(segment) => svg.draw_segment(segment),
                 Item::Circle(circle) => svg.draw_circle(circle),
+                Item::Angle(angle) => svg.draw_angle(angle),
             }
         }
 
@@ -37,6 +54,38 @@ impl Svg {
 }
 
 impl Svg {
+    /// Convert the given math string into SVG text content. Letters are italic and
+    /// lower indices are rendered as subscripts.
+    fn math_to_svg(math: &MathString) -> String {
+        let mut s = String::new();
+
+        for c in math.iter().copied() {
+            match c {
+                MathChar::Ascii(c) if c.is_ascii_alphabetic() => {
+                    s += &format!(r#"<tspan font-style="italic">{c}</tspan>"#);
+                }
+                MathChar::Ascii('<') => s += "&lt;",
+                MathChar::Ascii('>') => s += "&gt;",
+                MathChar::Ascii('&') => s += "&amp;",
+                MathChar::Ascii(c) => s.push(c),
+                MathChar::Special(MathSpecial::Quote) => s += "&quot;",
+                MathChar::Special(special) => {
+                    s += &format!(
+                        r#"<tspan font-style="italic">{}</tspan>"#,
+                        GREEK[special as usize]
+                    );
+                }
+                MathChar::SetIndex(MathIndex::Lower) => {
+                    s += r#"<tspan baseline-shift="sub" font-size="70%">"#;
+                }
+                MathChar::SetIndex(MathIndex::Normal) => s += "</tspan>",
+                MathChar::Prime => s.push('′'),
+            }
+        }
+
+        s
+    }
+
     /// The width of a line made with the given [`Style`]
     fn get_style_width(style: Style) -> &'static str {
         match style {
@@ -55,14 +104,31 @@ impl Svg {
         }
     }
 
+    /// Get the SVG color of an item. Items without a color are black.
+    fn get_color(color: Option<Color>) -> String {
+        color.map_or_else(|| String::from("black"), |color| color.to_string())
+    }
+
     /// Draw a styled segment delimited by two points.
-    fn draw_simple_segment(&mut self, (p1, p2): (Position, Position), style: Style) {
+    fn draw_simple_segment(
+        &mut self,
+        (p1, p2): (Position, Position),
+        style: Style,
+        color: Option<Color>,
+        arrow: bool,
+    ) {
         self.content += &format!(
             r#"
-                <line stroke-width="{}" stroke-dasharray="{}" stroke="black" x1="{}" x2="{}" y1="{}" y2="{}"/>
+                <line stroke-width="{}" stroke-dasharray="{}" stroke="{}"{} x1="{}" x2="{}" y1="{}" y2="{}"/>
             "#,
             Self::get_style_width(style),
             Self::get_style_dashing(style),
+            Self::get_color(color),
+            if arrow {
+                r#" marker-end="url(#arrow)""#
+            } else {
+                ""
+            },
             p1.x,
             p2.x,
             p1.y,
@@ -71,86 +137,191 @@ impl Svg {
     }
 
     fn begin(&mut self, figure: &Figure) {
+        self.width = figure.width;
+        self.height = figure.height;
+        self.font_size = f64::min(figure.width, figure.height) * LABEL_FONT_SCALE;
+
         self.content += &format!(
             r#"
-                <svg height="{}" width="{}" xmlns="http://www.w3.org/2000/svg">
+                <svg height="{}" width="{}" viewBox="0 0 {} {}" xmlns="http://www.w3.org/2000/svg">
                     <font>
                         <font-face font-family="New Computer Modern">
                         </font-face>
                     </font>
+                    <defs>
+                        <marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5"
+                            markerWidth="6" markerHeight="6" orient="auto-start-reverse">
+                            <path d="M 0 0 L 10 5 L 0 10 z" fill="context-stroke"/>
+                        </marker>
+                    </defs>
                 <g transform="translate(0,{})">
                 <g transform="scale(1,-1)">
             "#,
-            figure.width, figure.height, figure.width,
+            figure.height, figure.width, figure.width, figure.height, figure.height,
         );
     }
 
     fn draw_point(&mut self, point: &PointItem) {
         let pos = point.position;
         self.content += &format!(
-            r#"<circle cx="{}" cy="{}" fill="black" r="1"/>"#,
-            pos.x, pos.y
+            r#"<circle cx="{}" cy="{}" fill="{}" r="{}"/>"#,
+            pos.x,
+            pos.y,
+            Self::get_color(point.color),
+            point.size
         );
 
-        if let Some(label) = &point.label {
+        self.draw_label(point.label.as_ref());
+    }
+
+    /// Draw an item's label, if it has one. The label is centered at its position,
+    /// moved inwards if it would stick out of the canvas.
+    fn draw_label(&mut self, label: Option<&Label>) {
+        if let Some(label) = label {
+            // A rough estimate of the label's half-extents.
+            #[allow(clippy::cast_precision_loss)]
+            let half_width = label.content.len() as f64 * self.font_size * 0.3;
+            let half_height = self.font_size * 0.6;
+
+            let x = label
+                .position
+                .x
+                .clamp(half_width, (self.width - half_width).max(half_width));
+            let y = label
+                .position
+                .y
+                .clamp(half_height, (self.height - half_height).max(half_height));
+
             self.content += &format!(
                 r#"
                 <text transform="scale(1,-1)"
                     text-anchor="middle" dominant-baseline="middle"
-                    style="font-family: 'Computer Modern'" font-size="10px"
+                    style="font-family: 'Computer Modern'" font-size="{}px"
                     stroke="black" stroke-width="0" x="{}" y="-{}">{}
                 </text>
             "#,
-                label.position.x, label.position.y, label.content
+                self.font_size,
+                x,
+                y,
+                Self::math_to_svg(&label.content)
             );
         }
     }
 
     fn draw_line(&mut self, line: &LineItem) {
-        self.draw_simple_segment(line.points, line.style);
+        self.draw_simple_segment(line.points, line.style, line.color, false);
+        self.draw_label(line.label.as_ref());
     }
 
     fn draw_ray(&mut self, ray: &TwoPointItem) {
-        self.draw_simple_segment(ray.points, ray.style);
+        self.draw_simple_segment(ray.points, ray.style, ray.color, ray.arrow);
+        self.draw_label(ray.label.as_ref());
     }
 
     fn draw_segment(&mut self, segment: &TwoPointItem) {
-        self.draw_simple_segment(segment.points, segment.style);
-    }
-
-    // fn draw_angle(&mut self, angle: &RenderedAngle) {
-    //     let x: u32 = 45;
-    //     self.content += &format!(
-    //         r#"
-    //             <g transform="translate({}, {}) rotate({}, 0, 0)" fill="transparent">
-    //                 <path stroke-dasharray="{}" d="M {}, 0 A 45, 45, 0, 0, 0, {}, {}" stroke="black" stroke-width="{}"/>
-    //             </g>
-    //         "#,
-    //         angle.points.1.real,
-    //         angle.points.1.imaginary,
-    //         geometry::get_line(angle.points.1, angle.points.0)
-    //             .direction
-    //             .arg()
-    //             .to_degrees(),
-    //         Self::get_style_dashing(angle.item),
-    //         x, // It should probably be a constant. For now, we will leave it like this.
-    //         angle.angle_value.cos() * 45.0,
-    //         -angle.angle_value.sin() * 45.0,
-    //         style_width(angle.style),
-    //     );
-    // }
+        self.draw_simple_segment(segment.points, segment.style, segment.color, segment.arrow);
+        self.draw_label(segment.label.as_ref());
+    }
+
+    fn draw_angle(&mut self, angle: &AngleItem) {
+        self.draw_label(angle.label.as_ref());
+
+        if angle.arcs == 0 {
+            return;
+        }
+
+        if angle.is_right {
+            self.draw_right_angle(angle);
+            return;
+        }
+
+        // Positive sweep is counterclockwise, which is the positive-angle direction in figure space.
+        let large_arc = u8::from((angle.end - angle.start).abs() > PI);
+        let sweep = u8::from(angle.end > angle.start);
+
+        // Arcs are shrunk to fit on short arms.
+        let origin = angle.points.1;
+        let arm = |p: Position| f64::hypot(p.x - origin.x, p.y - origin.y);
+        let shorter_arm = f64::min(arm(angle.points.0), arm(angle.points.2));
+        let outermost = ANGLE_ARC_RADIUS + f64::from(angle.arcs - 1) * ANGLE_ARC_SPACING;
+        let scale = f64::min(1.0, shorter_arm * ANGLE_ARC_ARM_FRACTION / outermost);
+
+        for i in 0..angle.arcs {
+            let radius = (ANGLE_ARC_RADIUS + f64::from(i) * ANGLE_ARC_SPACING) * scale;
+            let arc_point = |direction: f64| {
+                origin
+                    + Position {
+                        x: direction.cos(),
+                        y: direction.sin(),
+                    } * radius
+            };
+            let start = arc_point(angle.start);
+            let end = arc_point(angle.end);
+
+            self.content += &format!(
+                r#"
+                <path d="M {} {} A {} {} 0 {} {} {} {}" stroke="{}" stroke-width="{}" stroke-dasharray="{}" fill="transparent"/>
+            "#,
+                start.x,
+                start.y,
+                radius,
+                radius,
+                large_arc,
+                sweep,
+                end.x,
+                end.y,
+                Self::get_color(angle.color),
+                Self::get_style_width(angle.style),
+                Self::get_style_dashing(angle.style),
+            );
+        }
+    }
+
+    /// Draw a square marking a right angle.
+    fn draw_right_angle(&mut self, angle: &AngleItem) {
+        let origin = angle.points.1;
+        let arm1 = Position {
+            x: angle.start.cos(),
+            y: angle.start.sin(),
+        } * RIGHT_ANGLE_MARK_SIZE;
+        let arm2 = Position {
+            x: angle.end.cos(),
+            y: angle.end.sin(),
+        } * RIGHT_ANGLE_MARK_SIZE;
+
+        let p1 = origin + arm1;
+        let corner = origin + arm1 + arm2;
+        let p2 = origin + arm2;
+
+        self.content += &format!(
+            r#"
+                <path d="M {} {} L {} {} L {} {}" stroke="{}" stroke-width="{}" stroke-dasharray="{}" fill="transparent"/>
+            "#,
+            p1.x,
+            p1.y,
+            corner.x,
+            corner.y,
+            p2.x,
+            p2.y,
+            Self::get_color(angle.color),
+            Self::get_style_width(angle.style),
+            Self::get_style_dashing(angle.style),
+        );
+    }
 
     fn draw_circle(&mut self, circle: &CircleItem) {
         self.content += &format!(
             r#"
-                <circle cx="{}" cy="{}" r="{}" stroke="black" stroke-width="{}" stroke-dasharray="{}" fill="transparent"/>
+                <circle cx="{}" cy="{}" r="{}" stroke="{}" stroke-width="{}" stroke-dasharray="{}" fill="transparent"/>
             "#,
             circle.center.x,
             circle.center.y,
             circle.radius,
+            Self::get_color(circle.color),
             Self::get_style_width(circle.style),
             Self::get_style_dashing(circle.style),
         );
+        self.draw_label(circle.label.as_ref());
     }
 
     fn end(&mut self) {
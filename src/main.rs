@@ -1,22 +1,18 @@
 #![warn(clippy::pedantic)]
 
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
 use clap::{Parser, ValueEnum};
 use crossterm::{cursor, terminal, ExecutableCommand, QueueableCommand};
-use geo_aid_geogebra::Geogebra;
 use geo_aid_internal::engine::glide::Glide;
 use geo_aid_internal::engine::rage::GenParams;
-use geo_aid_internal::engine::{glide, rage};
-use geo_aid_internal::projector;
+use geo_aid_internal::engine::{glide, rage, RuleQuality};
+use geo_aid_internal::format::{render, Format};
 use geo_aid_internal::script::figure::Generated;
 use geo_aid_internal::{
     engine::rage::Rage,
     script::cli::{Diagnostic, DiagnosticKind},
     script::math,
 };
-use geo_aid_json::Json;
-use geo_aid_latex::Latex;
-use geo_aid_plaintext::Plaintext;
-use geo_aid_svg::Svg;
 use std::time::Duration;
 use std::{
     fs::{self, File},
@@ -59,7 +55,7 @@ struct Args {
     #[arg(long, short, default_value_t = 2.0)]
     strictness: f64,
     /// Target formats.
-    #[arg(long, short, default_value = "svg")]
+    #[arg(long, short, default_value = "svg", value_parser = format_parser())]
     format: Vec<Format>,
     /// Canvas width
     #[arg(long)]
@@ -70,6 +66,18 @@ struct Args {
     /// Where to put the log output
     #[arg(long, short)]
     log: Option<PathBuf>,
+    /// The seed for the random number generator. Makes the generation reproducible.
+    #[arg(long)]
+    seed: Option<u64>,
+    /// The maximal number of iterations. Unlimited if not given.
+    #[arg(long)]
+    max_iterations: Option<usize>,
+    /// The quality (from 0 to 1) at which generation stops early.
+    #[arg(long)]
+    target_quality: Option<f64>,
+    /// Print the quality of every rule after generation. Helps find rules fighting each other.
+    #[arg(long)]
+    rule_qualities: bool,
     #[arg(long, hide = true)]
     markdown_help: Option<PathBuf>,
 }
@@ -82,24 +90,22 @@ enum Engine {
     Glide,
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
-enum Format {
-    /// LaTeX + tikz + tikz-euclide.
-    Latex,
-    /// Simple SVG
-    Svg,
-    /// JSON (machine-readable) format according to the Schema included in the repository.
-    Json,
-    /// Plain text (human-readable) format.
-    Plaintext,
-    /// Geogebra workspace format (*.ggb)
-    Geogebra,
+/// Parses the target formats, listing them with their descriptions in the help message.
+fn format_parser() -> impl TypedValueParser<Value = Format> {
+    PossibleValuesParser::new(
+        Format::ALL.map(|format| PossibleValue::new(format.name()).help(format.description())),
+    )
+    .map(|name| name.parse::<Format>().unwrap())
 }
 
+/// Rules with quality below this are reported as violated.
+const VIOLATION_THRESHOLD: f64 = 0.5;
+
 struct GenerationResult {
     generated: Generated,
     total_quality: f64,
     time: Duration,
+    rules: Vec<RuleQuality>,
 }
 
 #[allow(clippy::too_many_lines)]
@@ -163,6 +169,7 @@ fn main() {
         generated,
         total_quality,
         time,
+        rules,
     } = match args.engine {
         Engine::Rage => {
             let mut rage = Rage::new(
@@ -170,6 +177,7 @@ fn main() {
                     strictness: args.strictness,
                     samples: args.samples,
                     worker_count: args.worker_count,
+                    seed: args.seed,
                 },
                 &intermediate,
             );
@@ -182,6 +190,8 @@ fn main() {
                 max_adjustment: args.adjustment_max,
                 mean_count: args.mean_count,
                 delta_max_mean: args.delta_max_mean,
+                max_cycles: args.max_iterations,
+                target_quality: args.target_quality,
                 progress_update: Box::new(|quality| {
                     let mut stdout = io::stdout();
                     stdout
@@ -200,6 +210,7 @@ fn main() {
             GenerationResult {
                 time,
                 total_quality: rage.gen().get_total_quality(),
+                rules: rage.get_rule_qualities(),
                 generated: rage.get_figure(),
             }
         }
@@ -211,6 +222,9 @@ fn main() {
                     worker_count: args.worker_count,
                     mean_count: args.mean_count,
                     max_mean_delta: args.delta_max_mean,
+                    seed: args.seed,
+                    max_iterations: args.max_iterations,
+                    target_quality: args.target_quality,
                 },
                 &intermediate,
             );
@@ -237,6 +251,7 @@ fn main() {
                 time,
                 generated: glide.get_figure(),
                 total_quality: glide.get_total_quality(),
+                rules: glide.get_rule_qualities(),
             }
         }
     };
@@ -244,39 +259,53 @@ fn main() {
     let flags = Arc::new(intermediate.flags);
 
     for format in args.format.iter().copied() {
-        let width = args.width.unwrap_or(match format {
-            Format::Json => 1.0,
-            Format::Geogebra | Format::Plaintext => 5.0,
-            Format::Latex => 10.0,
-            Format::Svg => 500.0,
-        });
+        let width = args.width.unwrap_or(format.default_width());
         let height = args.height.unwrap_or(width);
 
-        let rendered = projector::project(generated.clone(), &flags, (width, height));
-
-        let final_path = target_path.join(target_name).with_extension(match format {
-            Format::Latex => "tex",
-            Format::Svg => "svg",
-            Format::Json => "json",
-            Format::Plaintext => "txt",
-            Format::Geogebra => "ggb",
-        });
-
-        match File::create(&final_path) {
-            Ok(mut file) => {
-                let res = match format {
-                    Format::Latex => file.write_all(Latex::draw(&rendered).as_bytes()),
-                    Format::Json => file.write_all(Json::draw(&rendered).as_bytes()),
-                    Format::Svg => file.write_all(Svg::draw(&rendered).as_bytes()),
-                    Format::Plaintext => file.write_all(Plaintext::draw(&rendered).as_bytes()),
-                    Format::Geogebra => Geogebra::draw(&rendered, file),
-                };
-
-                if let Err(err) = res {
-                    println!("Failed to write a file: {err}");
-                }
-            }
-            Err(err) => println!("Failed to write a file: {err}"),
+        let final_path = target_path
+            .join(target_name)
+            .with_extension(format.extension());
+
+        if let Err(err) = render(
+            format,
+            &final_path,
+            (width, height),
+            generated.clone(),
+            &flags,
+        ) {
+            println!("Failed to write a file: {err}");
+        }
+    }
+
+    if args.rule_qualities {
+        println!("Rule qualities:");
+
+        for rule in &rules {
+            println!(
+                "  {} (quality {:.2}%)",
+                rule.source.as_deref().unwrap_or("point inequality"),
+                rule.quality * 100.0
+            );
+        }
+    }
+
+    let violated: Vec<_> = rules
+        .iter()
+        .filter(|rule| rule.quality < VIOLATION_THRESHOLD)
+        .collect();
+
+    if !violated.is_empty() {
+        println!(
+            "Warning: the figure does not satisfy {} rule(s). It may be unreliable:",
+            violated.len()
+        );
+
+        for rule in &violated {
+            println!(
+                "  {} (quality {:.2}%)",
+                rule.source.as_deref().unwrap_or("point inequality"),
+                rule.quality * 100.0
+            );
         }
     }
 